@@ -0,0 +1,125 @@
+//! Checksum algorithms for the `checksum` field attribute.
+//!
+//! The derived structure computes the checksum over a range of its packed bytes and
+//! packs it into the field. Unpacking fails with `PackingError::ChecksumMismatch` if
+//! the field doesn't match. Any function with the signature `fn(&[u8]) -> T` can be
+//! used, where the field's type implements `From<T>`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use packed_struct::prelude::*;
+//!
+//! #[derive(PackedStruct, Debug, PartialEq)]
+//! #[packed_struct(endian="msb")]
+//! pub struct Frame {
+//!     address: u8,
+//!     value: u16,
+//!     #[packed_field(checksum="crc16_ccitt", over="0..3")]
+//!     crc: u16,
+//!     #[packed_field(checksum="inverted_sum")]
+//!     check: u8
+//! }
+//!
+//! fn inverted_sum(data: &[u8]) -> u8 {
+//!     !packed_struct::checksum::sum8(data)
+//! }
+//!
+//! // the checksums are computed when packing
+//! let frame = Frame { address: 0x01, value: 0x0203, crc: 0, check: 0 };
+//! let packed = frame.pack().unwrap();
+//! assert_eq!([0x01, 0x02, 0x03, 0xAD, 0xAD, 0x9F], packed);
+//!
+//! let unpacked = Frame::unpack(&packed).unwrap();
+//! assert_eq!(0xADAD, unpacked.crc);
+//!
+//! let mut corrupted = packed;
+//! corrupted[1] = 0x12;
+//! assert_eq!(Err(PackingError::ChecksumMismatch), Frame::unpack(&corrupted));
+//! ```
+
+/// CRC-8/SMBUS, polynomial 0x07.
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0u8;
+    for b in data {
+        crc ^= b;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+fn crc16_msb_first(data: &[u8], init: u16) -> u16 {
+    let mut crc = init;
+    for b in data {
+        crc ^= (*b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x1021 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// CRC-16/CCITT-FALSE, polynomial 0x1021, initial value 0xFFFF.
+pub fn crc16_ccitt(data: &[u8]) -> u16 {
+    crc16_msb_first(data, 0xFFFF)
+}
+
+/// CRC-16/XMODEM, polynomial 0x1021, initial value 0.
+pub fn crc16_xmodem(data: &[u8]) -> u16 {
+    crc16_msb_first(data, 0)
+}
+
+/// CRC-16/MODBUS, reflected polynomial 0x8005, initial value 0xFFFF. Modbus transmits
+/// it with the least significant byte first.
+pub fn crc16_modbus(data: &[u8]) -> u16 {
+    let mut crc = 0xFFFFu16;
+    for b in data {
+        crc ^= *b as u16;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xA001 } else { crc >> 1 };
+        }
+    }
+    crc
+}
+
+/// CRC-32 as used by Ethernet and zlib, reflected polynomial 0x04C11DB7.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for b in data {
+        crc ^= *b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+/// Sum of the bytes, modulo 256.
+pub fn sum8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |s, b| s.wrapping_add(*b))
+}
+
+/// Sum of the bytes, modulo 65536.
+pub fn sum16(data: &[u8]) -> u16 {
+    data.iter().fold(0, |s, b| s.wrapping_add(*b as u16))
+}
+
+/// All of the bytes XOR-ed together.
+pub fn xor8(data: &[u8]) -> u8 {
+    data.iter().fold(0, |s, b| s ^ b)
+}
+
+#[test]
+fn test_checksum_check_values() {
+    let data = b"123456789";
+    assert_eq!(0xF4, crc8(data));
+    assert_eq!(0x29B1, crc16_ccitt(data));
+    assert_eq!(0x31C3, crc16_xmodem(data));
+    assert_eq!(0x4B37, crc16_modbus(data));
+    assert_eq!(0xCBF4_3926, crc32(data));
+    assert_eq!(0xDD, sum8(data));
+    assert_eq!(0x01DD, sum16(data));
+    assert_eq!(0x31, xor8(data));
+}
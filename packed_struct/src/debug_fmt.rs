@@ -6,6 +6,12 @@ use crate::internal_prelude::v1::*;
 pub trait PackedStructDebug {
     fn fmt_fields(&self, fmt: &mut Formatter) -> Result<(), FmtError>;
     fn packed_struct_display_header() -> &'static str;
+
+    /// The fields and their bits within the packed structure, with the fields of
+    /// flattened structures listed individually. Used by the runtime formatter.
+    fn debug_fields(&self) -> Vec<DebugBitField<'static>> {
+        Vec::new()
+    }
 }
 
 pub struct DebugBinaryByteSlice<'a> {
@@ -1,7 +1,9 @@
 #[cfg(not(feature="std"))]
+#[allow(unused_imports)]
 #[path = "no_std.rs"]
 pub mod v1;
 
 #[cfg(feature="std")]
+#[allow(unused_imports)]
 #[path = "std.rs"]
 pub mod v1;
\ No newline at end of file
@@ -10,7 +10,7 @@ pub use core::ops::Range;
 pub use core::num::Wrapping;
 pub use core::cmp::*;
 pub use core::mem;
-pub use core::intrinsics::write_bytes;
+pub use core::ptr::write_bytes;
 pub use core::ops::Deref;
 pub use core::slice;
 pub use core::hash::{Hash, Hasher};
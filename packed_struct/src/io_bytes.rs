@@ -0,0 +1,165 @@
+//! Integration with the `bytes` crate's buffers.
+//!
+//! The `Buf` and `BufMut` extensions copy through the buffer's chunks, so they
+//! work with non-contiguous buffers like chains and ring buffers.
+//!
+//! `Bytes` and `BytesMut` can also be used as dynamically sized members of tuples.
+//! A plain slice can't be shared, so `unpack_from_slice` copies the payload once.
+//! `UnpackBytes` splits a received `Bytes` frame instead, the payload then shares
+//! the frame's allocation.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bytes::{Buf, Bytes, BytesMut};
+//! use packed_struct::prelude::*;
+//! use packed_struct::io_bytes::{BufPacked, BufMutPacked};
+//!
+//! #[derive(PackedStruct, Debug, PartialEq)]
+//! #[packed_struct(endian="msb")]
+//! pub struct Header {
+//!     kind: u8,
+//!     length: u16
+//! }
+//!
+//! let mut out = BytesMut::new();
+//! out.put_packed(&Header { kind: 2, length: 3 }).unwrap();
+//! out.extend_from_slice(&[0xAA, 0xBB, 0xCC]);
+//!
+//! let mut frame: Bytes = out.freeze();
+//! let header: Header = frame.get_packed().unwrap();
+//! assert_eq!(Header { kind: 2, length: 3 }, header);
+//! // the remainder shares the original allocation
+//! assert_eq!(&[0xAA, 0xBB, 0xCC], &frame[..]);
+//! ```
+//!
+//! # Example without copying the payload
+//!
+//! ```rust
+//! use bytes::Bytes;
+//! use packed_struct::prelude::*;
+//! use packed_struct::io_bytes::UnpackBytes;
+//!
+//! #[derive(PackedStruct, Debug, PartialEq)]
+//! pub struct Header {
+//!     kind: u8
+//! }
+//!
+//! let frame = Bytes::from_static(&[0x02, 0xAA, 0xBB]);
+//! let (header, payload) = <(Header, Bytes)>::unpack_from_bytes(frame).unwrap();
+//! assert_eq!(Header { kind: 2 }, header);
+//! assert_eq!(&[0xAA, 0xBB], &payload[..]);
+//! ```
+
+use crate::{PackedStruct, PackedStructSlice, PackingError, PackingResult};
+use crate::types_bits::ByteArray;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+/// Unpacks structures from a `bytes::Buf`, advancing it.
+pub trait BufPacked: Buf {
+    /// Unpacks the structure from the front of the buffer. The buffer is left
+    /// untouched if it doesn't hold enough bytes.
+    fn get_packed<T: PackedStruct>(&mut self) -> PackingResult<T> {
+        let mut buf = <T::ByteArray as ByteArray>::new(0);
+        if self.remaining() < buf.as_bytes_slice().len() {
+            return Err(PackingError::BufferTooSmall);
+        }
+        self.copy_to_slice(buf.as_mut_bytes_slice());
+        T::unpack(&buf)
+    }
+}
+
+impl<B> BufPacked for B where B: Buf + ?Sized { }
+
+/// Packs structures into a `bytes::BufMut`, advancing it.
+pub trait BufMutPacked: BufMut {
+    /// Packs the structure and appends it to the buffer. Growable buffers are
+    /// extended as needed, fixed ones fail if there isn't enough space left.
+    fn put_packed<T: PackedStruct>(&mut self, s: &T) -> PackingResult<()> {
+        let packed = s.pack()?;
+        let packed = packed.as_bytes_slice();
+        if self.remaining_mut() < packed.len() {
+            return Err(PackingError::BufferTooSmall);
+        }
+        self.put_slice(packed);
+        Ok(())
+    }
+}
+
+impl<B> BufMutPacked for B where B: BufMut + ?Sized { }
+
+/// Unpacks from a `Bytes` frame. The `Bytes` payload is split off the frame
+/// instead of being copied.
+pub trait UnpackBytes: Sized {
+    fn unpack_from_bytes(src: Bytes) -> PackingResult<Self>;
+}
+
+impl UnpackBytes for Bytes {
+    fn unpack_from_bytes(src: Bytes) -> PackingResult<Self> {
+        Ok(src)
+    }
+}
+
+/// A header followed by the payload
+impl<H> UnpackBytes for (H, Bytes) where H: PackedStructSlice {
+    fn unpack_from_bytes(src: Bytes) -> PackingResult<Self> {
+        let (header, rest) = H::unpack_from_prefix(&src)?;
+        let payload = src.slice((src.len() - rest.len())..);
+        Ok((header, payload))
+    }
+}
+
+/// A header, the payload and a statically sized trailer
+impl<H, F> UnpackBytes for (H, Bytes, F) where H: PackedStructSlice, F: PackedStructSlice {
+    fn unpack_from_bytes(src: Bytes) -> PackingResult<Self> {
+        let trailer_size = F::packed_bytes_size(None)?;
+        let (header, rest) = H::unpack_from_prefix(&src)?;
+        if rest.len() < trailer_size {
+            return Err(PackingError::BufferTooSmall);
+        }
+
+        let start = src.len() - rest.len();
+        let end = src.len() - trailer_size;
+        let trailer = F::unpack_from_slice(&src[end..])?;
+        Ok((header, src.slice(start..end), trailer))
+    }
+}
+
+/// A dynamically sized payload, copied as-is
+impl PackedStructSlice for Bytes {
+    fn pack_to_slice(&self, output: &mut [u8]) -> PackingResult<()> {
+        if output.len() != self.len() {
+            return Err(PackingError::BufferSizeMismatch { expected: self.len(), actual: output.len() });
+        }
+        output.copy_from_slice(self);
+        Ok(())
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> PackingResult<Self> {
+        Ok(Bytes::copy_from_slice(src))
+    }
+
+    fn packed_bytes_size(opt_self: Option<&Self>) -> PackingResult<usize> {
+        opt_self.map(|s| s.len()).ok_or(PackingError::InstanceRequiredForSize)
+    }
+}
+
+/// A dynamically sized payload, copied as-is
+impl PackedStructSlice for BytesMut {
+    fn pack_to_slice(&self, output: &mut [u8]) -> PackingResult<()> {
+        if output.len() != self.len() {
+            return Err(PackingError::BufferSizeMismatch { expected: self.len(), actual: output.len() });
+        }
+        output.copy_from_slice(self);
+        Ok(())
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> PackingResult<Self> {
+        Ok(BytesMut::from(src))
+    }
+
+    fn packed_bytes_size(opt_self: Option<&Self>) -> PackingResult<usize> {
+        opt_self.map(|s| s.len()).ok_or(PackingError::InstanceRequiredForSize)
+    }
+}
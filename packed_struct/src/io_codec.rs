@@ -0,0 +1,205 @@
+//! `tokio_util::codec` framing for packed structures.
+//!
+//! [`PackedCodec`] frames structures with a static size, [`PackedLengthCodec`]
+//! frames dynamically sized ones behind a packed length prefix. Both decoders wait
+//! until a whole frame has been buffered.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bytes::BytesMut;
+//! use tokio_util::codec::{Decoder, Encoder};
+//! use packed_struct::prelude::*;
+//! use packed_struct::io_codec::PackedLengthCodec;
+//!
+//! type Length = MsbInteger<u16, packed_bits::Bits::<16>, Integer<u16, packed_bits::Bits::<16>>>;
+//!
+//! let mut codec = PackedLengthCodec::<Length, Vec<u8>>::new();
+//! let mut buf = BytesMut::new();
+//! codec.encode(vec![0xAA, 0xBB, 0xCC], &mut buf).unwrap();
+//! assert_eq!(&[0x00, 0x03, 0xAA, 0xBB, 0xCC], &buf[..]);
+//!
+//! let mut partial = buf.split_to(4);
+//! assert_eq!(None, codec.decode(&mut partial).unwrap());
+//! partial.unsplit(buf);
+//! assert_eq!(Some(vec![0xAA, 0xBB, 0xCC]), codec.decode(&mut partial).unwrap());
+//! ```
+
+use crate::internal_prelude::v1::*;
+
+use crate::{PackedStruct, PackedStructSlice, PackingError};
+use crate::io_bytes::{BufPacked, BufMutPacked};
+use crate::types_bits::ByteArray;
+use crate::types_length::PackedLength;
+
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::{Decoder, Encoder};
+
+/// Errors surfaced by the packed codecs.
+#[derive(Debug)]
+pub enum PackedCodecError {
+    /// The underlying transport reported an error.
+    Io(io::Error),
+    /// The frame couldn't be packed or unpacked.
+    Packing(PackingError),
+    /// The length prefix exceeds the codec's configured maximum.
+    FrameTooLarge { length: usize, max_length: usize }
+}
+
+impl From<io::Error> for PackedCodecError {
+    fn from(e: io::Error) -> Self {
+        PackedCodecError::Io(e)
+    }
+}
+
+impl From<PackingError> for PackedCodecError {
+    fn from(e: PackingError) -> Self {
+        PackedCodecError::Packing(e)
+    }
+}
+
+impl Display for PackedCodecError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PackedCodecError::Io(e) => write!(f, "I/O error: {}", e),
+            PackedCodecError::Packing(e) => write!(f, "Packing error: {}", e),
+            PackedCodecError::FrameTooLarge { length, max_length } => write!(f, "Frame of {} bytes exceeds the maximum of {} bytes", length, max_length)
+        }
+    }
+}
+
+impl ::std::error::Error for PackedCodecError {
+    fn source(&self) -> Option<&(dyn ::std::error::Error + 'static)> {
+        match self {
+            PackedCodecError::Io(e) => Some(e),
+            PackedCodecError::Packing(e) => Some(e),
+            PackedCodecError::FrameTooLarge { .. } => None
+        }
+    }
+}
+
+/// Encodes and decodes structures with a statically known packed size.
+#[derive(Debug, Copy, Clone)]
+pub struct PackedCodec<T> {
+    packed: PhantomData<T>
+}
+
+impl<T> PackedCodec<T> {
+    pub fn new() -> Self {
+        PackedCodec { packed: PhantomData }
+    }
+}
+
+impl<T> Default for PackedCodec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Decoder for PackedCodec<T> where T: PackedStruct {
+    type Item = T;
+    type Error = PackedCodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, PackedCodecError> {
+        let len = <T::ByteArray as ByteArray>::len();
+        if src.len() < len {
+            src.reserve(len - src.len());
+            return Ok(None);
+        }
+
+        Ok(Some(src.get_packed()?))
+    }
+}
+
+impl<T> Encoder<T> for PackedCodec<T> where T: PackedStruct {
+    type Error = PackedCodecError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), PackedCodecError> {
+        dst.reserve(<T::ByteArray as ByteArray>::len());
+        Ok(dst.put_packed(&item)?)
+    }
+}
+
+/// Encodes and decodes dynamically sized structures, prefixed with their length in
+/// bytes. The prefix `L` is a packed integer, for example a `u8` or a `MsbInteger`.
+#[derive(Debug, Copy, Clone)]
+pub struct PackedLengthCodec<L, T> {
+    max_length: usize,
+    packed: PhantomData<(L, T)>
+}
+
+impl<L, T> PackedLengthCodec<L, T> {
+    /// A codec that accepts any length its prefix can describe.
+    pub fn new() -> Self {
+        Self::with_max_length(usize::MAX)
+    }
+
+    /// A codec that rejects frames longer than `max_length` bytes, without buffering them.
+    pub fn with_max_length(max_length: usize) -> Self {
+        PackedLengthCodec { max_length, packed: PhantomData }
+    }
+
+    pub fn max_length(&self) -> usize {
+        self.max_length
+    }
+}
+
+impl<L, T> Default for PackedLengthCodec<L, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<L, T> Decoder for PackedLengthCodec<L, T> where L: PackedLength, T: PackedStructSlice {
+    type Item = T;
+    type Error = PackedCodecError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<T>, PackedCodecError> {
+        let prefix_len = <L::ByteArray as ByteArray>::len();
+        if src.len() < prefix_len {
+            src.reserve(prefix_len - src.len());
+            return Ok(None);
+        }
+
+        let length = L::unpack_from_slice(&src[..prefix_len])?.to_length()?;
+        if length > self.max_length {
+            return Err(PackedCodecError::FrameTooLarge { length, max_length: self.max_length });
+        }
+
+        let frame_len = prefix_len + length;
+        if src.len() < frame_len {
+            src.reserve(frame_len - src.len());
+            return Ok(None);
+        }
+
+        src.advance(prefix_len);
+        let payload = src.split_to(length);
+        Ok(Some(T::unpack_from_slice(&payload)?))
+    }
+}
+
+impl<L, T> Encoder<T> for PackedLengthCodec<L, T> where L: PackedLength, T: PackedStructSlice {
+    type Error = PackedCodecError;
+
+    fn encode(&mut self, item: T, dst: &mut BytesMut) -> Result<(), PackedCodecError> {
+        let length = T::packed_bytes_size(Some(&item))?;
+        if length > self.max_length {
+            return Err(PackedCodecError::FrameTooLarge { length, max_length: self.max_length });
+        }
+
+        let prefix = L::from_length(length)?;
+        dst.reserve(<L::ByteArray as ByteArray>::len() + length);
+        let frame_start = dst.len();
+        dst.put_packed(&prefix)?;
+
+        let start = dst.len();
+        dst.resize(start + length, 0);
+        if let Err(e) = item.pack_to_slice(&mut dst[start..]) {
+            // don't leave a partial frame behind
+            dst.truncate(frame_start);
+            return Err(e.into());
+        }
+
+        Ok(())
+    }
+}
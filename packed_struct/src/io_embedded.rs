@@ -0,0 +1,137 @@
+//! Packing directly into and unpacking directly from `embedded-io` streams.
+//!
+//! The extension traits are implemented for every blocking `embedded_io::Read`
+//! and `embedded_io::Write` type, and with the `use_embedded_io_async` feature,
+//! for their `embedded_io_async` counterparts. The structure's byte array is
+//! kept on the stack, so no allocation is required.
+//!
+//! # Example
+//!
+//! ```rust
+//! use packed_struct::prelude::*;
+//! use packed_struct::io_embedded::{ReadPacked, WritePacked};
+//!
+//! #[derive(PackedStruct, Debug, PartialEq)]
+//! #[packed_struct(endian="msb")]
+//! pub struct Frame {
+//!     id: u8,
+//!     value: u16
+//! }
+//!
+//! let mut uart = [0; 3];
+//! (&mut uart[..]).write_packed(&Frame { id: 1, value: 0x1234 }).unwrap();
+//! assert_eq!([0x01, 0x12, 0x34], uart);
+//!
+//! let frame: Frame = (&uart[..]).read_packed().unwrap();
+//! assert_eq!(Frame { id: 1, value: 0x1234 }, frame);
+//! ```
+
+use crate::internal_prelude::v1::*;
+
+use crate::{PackedStruct, PackingError};
+use crate::types_bits::ByteArray;
+
+use embedded_io::{ErrorKind, ReadExactError};
+
+/// Errors that might occur while packing into or unpacking from a stream.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PackedIoError<E> {
+    /// The underlying stream reported an error.
+    Io(E),
+    /// The stream ended before the whole structure was read.
+    UnexpectedEof,
+    /// The structure couldn't be packed or unpacked.
+    Packing(PackingError)
+}
+
+impl<E> From<PackingError> for PackedIoError<E> {
+    fn from(e: PackingError) -> Self {
+        PackedIoError::Packing(e)
+    }
+}
+
+impl<E> From<ReadExactError<E>> for PackedIoError<E> {
+    fn from(e: ReadExactError<E>) -> Self {
+        match e {
+            ReadExactError::UnexpectedEof => PackedIoError::UnexpectedEof,
+            ReadExactError::Other(e) => PackedIoError::Io(e)
+        }
+    }
+}
+
+impl<E> Display for PackedIoError<E> where E: Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[cfg(feature="std")]
+impl<E> ::std::error::Error for PackedIoError<E> where E: Debug { }
+
+impl<E> embedded_io::Error for PackedIoError<E> where E: embedded_io::Error {
+    fn kind(&self) -> ErrorKind {
+        match self {
+            PackedIoError::Io(e) => e.kind(),
+            PackedIoError::UnexpectedEof => ErrorKind::Other,
+            PackedIoError::Packing(_) => ErrorKind::InvalidData
+        }
+    }
+}
+
+/// Unpacks structures from a blocking `embedded_io::Read` stream.
+pub trait ReadPacked: embedded_io::Read {
+    /// Reads exactly as many bytes as the structure occupies and unpacks it.
+    fn read_packed<T: PackedStruct>(&mut self) -> Result<T, PackedIoError<Self::Error>> {
+        let mut buf = <T::ByteArray as ByteArray>::new(0);
+        self.read_exact(buf.as_mut_bytes_slice())?;
+        Ok(T::unpack(&buf)?)
+    }
+}
+
+impl<R> ReadPacked for R where R: embedded_io::Read + ?Sized { }
+
+/// Packs structures into a blocking `embedded_io::Write` stream.
+pub trait WritePacked: embedded_io::Write {
+    /// Packs the structure and writes all of its bytes.
+    fn write_packed<T: PackedStruct>(&mut self, s: &T) -> Result<(), PackedIoError<Self::Error>> {
+        let packed = s.pack()?;
+        self.write_all(packed.as_bytes_slice()).map_err(PackedIoError::Io)
+    }
+}
+
+impl<W> WritePacked for W where W: embedded_io::Write + ?Sized { }
+
+/// The same extension traits, for `embedded-io-async` streams. Kept in a separate
+/// module as most stream types implement both the blocking and the async traits.
+#[cfg(feature="use_embedded_io_async")]
+pub mod asynch {
+    use crate::PackedStruct;
+    use crate::types_bits::ByteArray;
+
+    use super::PackedIoError;
+
+    /// Unpacks structures from an `embedded_io_async::Read` stream.
+    #[allow(async_fn_in_trait)]
+    pub trait ReadPacked: embedded_io_async::Read {
+        /// Reads exactly as many bytes as the structure occupies and unpacks it.
+        async fn read_packed<T: PackedStruct>(&mut self) -> Result<T, PackedIoError<Self::Error>> {
+            let mut buf = <T::ByteArray as ByteArray>::new(0);
+            self.read_exact(buf.as_mut_bytes_slice()).await?;
+            Ok(T::unpack(&buf)?)
+        }
+    }
+
+    impl<R> ReadPacked for R where R: embedded_io_async::Read + ?Sized { }
+
+    /// Packs structures into an `embedded_io_async::Write` stream.
+    #[allow(async_fn_in_trait)]
+    pub trait WritePacked: embedded_io_async::Write {
+        /// Packs the structure and writes all of its bytes.
+        async fn write_packed<T: PackedStruct>(&mut self, s: &T) -> Result<(), PackedIoError<Self::Error>> {
+            let packed = s.pack()?;
+            self.write_all(packed.as_bytes_slice()).await.map_err(PackedIoError::Io)
+        }
+    }
+
+    impl<W> WritePacked for W where W: embedded_io_async::Write + ?Sized { }
+}
@@ -21,12 +21,19 @@
 //!  * Runtime packing visualization
 //!  * Nested packed types
 //!  * Arrays of packed structures as fields
-//!  * Reserved fields, their bits are always 0 or 1
+//!  * Reserved fields, their bits are always 0 or 1, optionally checked or preserved
+//!  * Constant fields, like magic numbers, that are checked when unpacking
+//!  * Checksums, parity bits and Hamming codes
 //!
 //! # Crate-level feature flags
 //!  * `std`: use the Rust standard library. Default.
 //!  * `alloc`: use the `alloc` crate for `no_std` + `alloc` scenarios. Requires nightly Rust.
 //!  * `use_serde`: add serialization support to the built-in helper types.
+//!  * `use_embedded_io`: pack into and unpack from blocking `embedded-io` streams.
+//!  * `use_embedded_io_async`: the same, for `embedded-io-async` streams. Requires Rust 1.75 for async functions in traits.
+//!  * `use_bytes`: `Buf` and `BufMut` extensions, `Bytes` and `BytesMut` as tuple payloads, unpacked from `Bytes` without copying.
+//!  * `use_tokio_util`: `tokio_util::codec` encoders and decoders. Implies `std` and `use_bytes`.
+//!  * `use_heapless`: `heapless::Vec` and `heapless::String` as dynamically sized members, without an allocator.
 //!  * `byte_types_64`, `byte_types_256`: enlarge the size of the generated array, byte and bit width types.
 //!
 //! # Sample usage
@@ -129,10 +136,28 @@
 //! ```bytes``` | ```0```, ```0..1```, ... | Same as above, multiplied by 8.
 //! ```size_bits``` | ```1```, ... | Specifies the size of the packed structure. Mandatory for certain types. Specifying a range of bits like ```bits="0..2"``` can substite the required usage of ```size_bits```.
 //! ```size_bytes``` | ```1```, ... | Same as above, multiplied by 8.
-//! ```element_size_bits``` | ```1```, ... | For packed arrays, specifies the size of a single element of the array. Explicitly stating the size of the entire array can substite the usage of this attribute.
+//! ```element_size_bits``` | ```1```, ... | For packed arrays, specifies the size of a single element of the array. Explicitly stating the size of the entire array can substite the usage of this attribute. Arrays of arrays, like ```[[bool; 8]; 2]```, are packed in row-major order and sized by their innermost elements.
 //! ```element_size_bytes``` | ```1```, ... | Same as above, multiplied by 8.
+//! ```element_stride_bits``` | ```4```, ... | For packed arrays, the distance between the starts of two elements. Other fields can be placed into the gaps between the elements.
+//! ```element_order``` | ```forward``` or ```reverse``` | For packed arrays. Reversed, the first element is placed at the end of the field, at the least significant bits. Natural for arrays in ```lsb0``` structures.
 //! ```ty``` | ```enum``` | Packing helper for primitive enums.
 //! ```endian``` | ```msb``` or ```lsb``` | Integer endianness. Applies to u16/i16 and larger types.
+//! ```present_bit``` | ```0```, ... | For ```Option<T>``` fields, the position of the bit that is set when the value is present. ```None``` packs a cleared bit and zeroed payload.
+//! ```none_value``` | ```0xFFFF```, ... | For ```Option<T>``` integer and enum fields, the raw value that stands for ```None```. Packing this value as ```Some``` fails.
+//! ```count``` | Field name | For dynamically sized fields, the statically sized field that holds the number of elements. Filled in automatically when packing.
+//! ```length_prefix``` | ```u8```, ```u16```, ```u32```, ```u64``` or a ```PackedLength``` type | For dynamically sized fields, the number of bytes they take up is packed in front of them, like for ```LengthPrefixed```.
+//! ```if``` | ```self.version >= 2```, ... | For ```Option<T>``` fields after the statically sized fields. The value is present when the condition holds, ```self.field``` refers to the fields unpacked before it.
+//! ```checksum``` | ```crc8```, ```crc16_ccitt```, ```crc16_xmodem```, ```crc16_modbus```, ```crc32```, ```sum8```, ```sum16```, ```xor8``` or a function path | Computes the field from the packed bytes when packing, verifies it when unpacking. See the ```checksum``` module.
+//! ```over``` | ```0..10```, ```0..=9```, ```2..``` | For ```checksum``` fields, the range of bytes that is checked. Defaults to all of the bytes in front of the field.
+//! ```with``` | Module path | A module with the functions ```pack(&T) -> PackingResult<[u8; N]>``` and ```unpack(&[u8; N]) -> PackingResult<T>``` that replace the field's encoding. ```N``` is the number of bytes that hold the field's bits, aligned to the least significant bit.
+//! ```flatten``` | | For nested packed structures. The runtime formatter shows their fields individually, named ```field.nested```. Only changes the debug output: the derive can't see the nested fields, so the layout, the other attributes and the type's documentation still treat the structure as a single field. Nested fields that don't fit into the field's bits aren't shown.
+//! ```overlay``` | | Another view of bits that belong to other fields, positioned explicitly. All of the views are unpacked from the same bits. Has to be within the bits of regular fields and can't overlap other overlays. The regular fields are packed, packing fails with ```PackingError::OverlayMismatch``` if an overlay doesn't match their bits.
+//! ```invert``` | | For single bit ```bool``` fields, an active-low bit: a cleared bit means ```true```.
+//! ```true_value```, ```false_value``` | ```0b1010```, ```0x5```, ... | For ```bool``` fields, the bit patterns of ```true``` and ```false```. Any other pattern fails to unpack with ```PackingError::InvalidValue```.
+//! ```skip``` | | The field isn't packed. It's initialized with ```Default``` when unpacking.
+//! ```default``` | Expression | For ```skip``` fields, the value used when unpacking. Can refer to the unpacked, statically sized fields by their name.
+//! ```parity``` | ```even``` or ```odd``` | For ```bool``` fields, a parity bit that is computed when packing and verified when unpacking.
+//! ```over_bits``` | ```0..=6```, ... | For ```parity``` fields, the range of bits that is covered, like ```bits```. Defaults to all of the bits in front of the field.
 //! 
 //! ## Bit and byte positioning
 //! 
@@ -195,6 +220,11 @@
 //! ```
 //!
 //! ## Nested packed types
+//!
+//! Without a position or a size, the size of a nested structure is inferred by the compiler
+//! from its ```ByteArray```. Such structures and the fields that follow them have to be byte
+//! aligned, and are placed after the explicitly positioned fields. An explicit size that
+//! doesn't match the nested structure's ```ByteArray``` fails to compile.
 //! 
 //! ```rust
 //! use packed_struct::prelude::*;
@@ -206,7 +236,6 @@
 //! }
 //! #[derive(PackedStruct, Debug, PartialEq)]
 //! pub struct Record {
-//!     #[packed_field(element_size_bytes="2")]
 //!     span: Duration,
 //!     events: u8,
 //! }
@@ -263,6 +292,46 @@
 //! }
 //! ```
 //! 
+//! ## Dynamically sized fields
+//!
+//! With the ```std``` or ```alloc``` feature, ```Vec<T>``` fields can follow the statically sized
+//! fields. Without an allocator, the ```use_heapless``` feature enables ```heapless::Vec<T, N>```
+//! and ```heapless::String<N>``` fields. Their length is either stored in another field as the number
+//! of elements, packed in front of the elements as the number of bytes, or for the last field,
+//! taken from the rest of the buffer. Such
+//! structures implement only ```PackedStructSlice```.
+//!
+//! ```rust
+//! use packed_struct::prelude::*;
+//!
+//! #[derive(PackedStruct, Debug, PartialEq)]
+//! #[packed_struct(endian="msb")]
+//! pub struct Packet {
+//!     kind: u8,
+//!     num_values: u8,
+//!     #[packed_field(count="num_values")]
+//!     values: Vec<u8>,
+//!     #[packed_field(length_prefix="u16")]
+//!     name: Vec<u8>
+//! }
+//!
+//! fn main() -> Result<(), PackingError> {
+//!     let packet = Packet {
+//!         kind: 1,
+//!         num_values: 0,
+//!         values: vec![0xAA, 0xBB],
+//!         name: b"x".to_vec()
+//!     };
+//!
+//!     let packed = packet.pack_to_vec()?;
+//!     assert_eq!(&[1, 2, 0xAA, 0xBB, 0, 1, b'x'][..], &packed[..]);
+//!
+//!     let unpacked = Packet::unpack_from_slice(&packed)?;
+//!     assert_eq!(2, unpacked.num_values);
+//!     Ok(())
+//! }
+//! ```
+//!
 //! # Primitive enums with simple discriminants
 //! 
 //! Supported backing integer types: ```u8```, ```u16```, ```u32```, ```u64```, ```i8```, ```i16```, ```i32```, ```i64```.
@@ -335,6 +404,7 @@ mod internal_prelude;
 
 #[macro_use]
 mod packing;
+mod packing_iter;
 
 mod primitive_enum;
 
@@ -343,19 +413,40 @@ pub use primitive_enum::*;
 #[cfg(any(feature="alloc", feature="std"))]
 pub mod debug_fmt;
 
+pub mod checksum;
+
 mod types_array;
 mod types_basic;
 mod types_bits;
+mod types_const;
+mod types_ecc;
 mod types_generic;
+mod types_length;
 mod types_num;
 mod types_reserved;
+mod types_string;
 
 pub mod types_tuples;
 
+pub mod stream;
+
 #[cfg(any(feature="alloc", feature="std"))]
 mod types_vec;
 
+#[cfg(feature="use_heapless")]
+mod types_heapless;
+
+#[cfg(feature="use_embedded_io")]
+pub mod io_embedded;
+
+#[cfg(feature="use_bytes")]
+pub mod io_bytes;
+
+#[cfg(feature="use_tokio_util")]
+pub mod io_codec;
+
 /// Implementations and wrappers for various packing types.
+#[allow(unused_imports)]
 pub mod types {
     pub use super::types_basic::*;
 
@@ -367,12 +458,17 @@ pub mod types {
     pub use super::types_num::*;
     pub use super::types_array::*;
     pub use super::types_reserved::*;
+    pub use super::types_const::*;
+    pub use super::types_ecc::*;
     pub use super::types_generic::*;
+    pub use super::types_length::*;
+    pub use super::types_string::*;
     #[cfg(any(feature="alloc", feature="std"))]
     pub use super::types_vec::*;
 }
 
 pub use self::packing::*;
+pub use self::packing_iter::*;
 
 /// The derivation macros for packing and enums.
 pub mod derive {
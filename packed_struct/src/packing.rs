@@ -1,6 +1,7 @@
 use crate::internal_prelude::v1::*;
 
 use crate::types_bits::ByteArray;
+use crate::packing_iter::PackedSliceIter;
 
 /// A structure that can be packed and unpacked from a byte array.
 /// 
@@ -34,6 +35,55 @@ pub trait PackedStructSlice where Self: Sized {
     /// Number of bytes that the type or this particular instance of this structure demands for packing or unpacking.
     fn packed_bytes_size(opt_self: Option<&Self>) -> PackingResult<usize>;
 
+    /// Number of bytes that the structure packed at the start of the buffer occupies. Only
+    /// dynamically sized structures that encode their own length, like `LengthPrefixed`,
+    /// can tell, others fail with `InstanceRequiredForSize`.
+    fn packed_bytes_size_from_prefix(src: &[u8]) -> PackingResult<usize> {
+        let _ = src;
+        Self::packed_bytes_size(None)
+    }
+
+    /// Unpacks the structure from the start of the buffer and returns the unconsumed
+    /// remainder. Dynamically sized structures that don't encode their own length
+    /// consume the whole buffer.
+    fn unpack_from_prefix(src: &[u8]) -> PackingResult<(Self, &[u8])> {
+        let size = match Self::packed_bytes_size_from_prefix(src) {
+            Ok(size) => size,
+            Err(PackingError::InstanceRequiredForSize) => src.len(),
+            Err(e) => return Err(e)
+        };
+        if src.len() < size {
+            return Err(PackingError::BufferTooSmall);
+        }
+
+        let (prefix, rest) = src.split_at(size);
+        Ok((Self::unpack_from_slice(prefix)?, rest))
+    }
+
+    /// Packs the structure into the start of the buffer and returns the unused remainder.
+    fn pack_to_prefix<'a>(&self, output: &'a mut [u8]) -> PackingResult<&'a mut [u8]> {
+        let size = Self::packed_bytes_size(Some(self))?;
+        if output.len() < size {
+            return Err(PackingError::BufferTooSmall);
+        }
+
+        let (prefix, rest) = output.split_at_mut(size);
+        self.pack_to_slice(prefix)?;
+        Ok(rest)
+    }
+
+    /// Lazily unpacks consecutive records of a statically sized structure. A trailing
+    /// partial record is reported as a `BufferModMismatch` error after the last record.
+    fn unpack_iter(src: &[u8]) -> PackedSliceIter<'_, Self> {
+        PackedSliceIter::new(src, false)
+    }
+
+    /// Lazily unpacks consecutive records of a statically sized structure. A trailing
+    /// partial record is skipped and can be retrieved with `remainder()`.
+    fn unpack_iter_exact(src: &[u8]) -> PackedSliceIter<'_, Self> {
+        PackedSliceIter::new(src, true)
+    }
+
     #[cfg(any(feature="alloc", feature="std"))]
     fn pack_to_vec(&self) -> PackingResult<Vec<u8>> {
         let size = Self::packed_bytes_size(Some(self))?;
@@ -56,6 +106,12 @@ pub enum PackingError {
     BufferSizeMismatch { expected: usize, actual: usize },
     BufferModMismatch { actual_size: usize, modulo_required: usize },
     SliceIndexingError { slice_len: usize },
+    CapacityExceeded { capacity: usize, required: usize },
+    ChecksumMismatch,
+    ConstMismatch { expected: u64, actual: u64 },
+    ReservedBitsMismatch,
+    OverlayMismatch,
+    UncorrectableBitErrors,
     InternalError
 }
 
@@ -77,7 +133,13 @@ impl ::std::error::Error for PackingError {
             PackingError::InstanceRequiredForSize => "This structure's packing size can't be determined statically, an instance is required.",
             PackingError::BufferModMismatch { .. } => "The structure's size is not a multiple of the item's size",
             PackingError::SliceIndexingError { .. } => "Failed to index into a slice",
-            PackingError::MoreThanOneDynamicType => "Only one dynamically sized type is supported in the tuple",
+            PackingError::MoreThanOneDynamicType => "Only one dynamically sized type without a length prefix is supported in the tuple",
+            PackingError::CapacityExceeded { .. } => "The collection's capacity is too small",
+            PackingError::ChecksumMismatch => "Checksum mismatch",
+            PackingError::ConstMismatch { .. } => "Unexpected value of a constant field",
+            PackingError::ReservedBitsMismatch => "Unexpected bits in a reserved field",
+            PackingError::OverlayMismatch => "An overlay doesn't match the fields that share its bits",
+            PackingError::UncorrectableBitErrors => "Too many flipped bits to correct",
             PackingError::InternalError => "Internal error"
         }
     }
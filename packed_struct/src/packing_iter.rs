@@ -0,0 +1,80 @@
+//! Lazy unpacking of consecutive, statically sized records from a slice.
+
+use crate::internal_prelude::v1::*;
+
+use crate::{PackedStructSlice, PackingError, PackingResult};
+
+/// An iterator that unpacks one record at a time. Created by
+/// [`PackedStructSlice::unpack_iter`] and [`PackedStructSlice::unpack_iter_exact`].
+pub struct PackedSliceIter<'a, T> {
+    records: &'a [u8],
+    remainder: &'a [u8],
+    item_size: usize,
+    error: Option<PackingError>,
+    unpacked: PhantomData<T>
+}
+
+impl<'a, T> PackedSliceIter<'a, T> where T: PackedStructSlice {
+    pub(crate) fn new(src: &'a [u8], exact: bool) -> Self {
+        let item_size = match T::packed_bytes_size(None) {
+            Ok(s) => s,
+            Err(e) => return Self::failed(src, e)
+        };
+
+        if item_size == 0 {
+            return Self::failed(src, PackingError::InstanceRequiredForSize);
+        }
+
+        let (records, remainder) = src.split_at(src.len() - (src.len() % item_size));
+        let error = if !exact && !remainder.is_empty() {
+            Some(PackingError::BufferModMismatch { actual_size: src.len(), modulo_required: item_size })
+        } else {
+            None
+        };
+
+        PackedSliceIter {
+            records,
+            remainder,
+            item_size,
+            error,
+            unpacked: PhantomData
+        }
+    }
+
+    fn failed(src: &'a [u8], error: PackingError) -> Self {
+        PackedSliceIter {
+            records: &[],
+            remainder: src,
+            item_size: 0,
+            error: Some(error),
+            unpacked: PhantomData
+        }
+    }
+
+    /// The trailing bytes that don't form a whole record.
+    pub fn remainder(&self) -> &'a [u8] {
+        self.remainder
+    }
+}
+
+impl<'a, T> Iterator for PackedSliceIter<'a, T> where T: PackedStructSlice {
+    type Item = PackingResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.records.is_empty() {
+            return self.error.take().map(Err);
+        }
+
+        let (record, rest) = self.records.split_at(self.item_size);
+        self.records = rest;
+        Some(T::unpack_from_slice(record))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.records.len().checked_div(self.item_size).unwrap_or(0);
+        let n = n + if self.error.is_some() { 1 } else { 0 };
+        (n, Some(n))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for PackedSliceIter<'a, T> where T: PackedStructSlice { }
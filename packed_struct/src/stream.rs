@@ -0,0 +1,228 @@
+//! Incremental decoding of packed frames from a lossy byte stream.
+//!
+//! [`PackedStreamDecoder`] buffers incoming chunks in a fixed-size array, so it
+//! works without an allocator. It searches for the start of a frame, optionally
+//! validates it, and yields every complete frame. After a corrupted frame the
+//! decoder drops a single byte and searches again, so it resynchronizes on the
+//! next valid frame.
+//!
+//! # Example
+//!
+//! ```rust
+//! use packed_struct::prelude::*;
+//! use packed_struct::stream::PackedStreamDecoder;
+//!
+//! #[derive(PackedStruct, Debug, PartialEq)]
+//! pub struct Frame {
+//!     sync: [u8; 2],
+//!     value: u8,
+//!     checksum: u8
+//! }
+//!
+//! fn xor_checksum(frame: &[u8]) -> bool {
+//!     frame[..3].iter().fold(0, |a, b| a ^ b) == frame[3]
+//! }
+//!
+//! let mut decoder = PackedStreamDecoder::<Frame, 16>::new().unwrap()
+//!     .with_sync(&[0xAA, 0x55])
+//!     .with_checksum(xor_checksum);
+//!
+//! // noise, a frame that was cut in half, and a corrupted frame
+//! let mut values = vec![];
+//! for chunk in [&[0x01, 0xAA][..], &[0x55, 0x07, 0xF8, 0xAA, 0x55], &[0x08, 0x00]] {
+//!     for frame in decoder.feed(chunk) {
+//!         match frame {
+//!             Ok(frame) => values.push(frame.value),
+//!             Err(e) => assert_eq!(PackingError::ChecksumMismatch, e)
+//!         }
+//!     }
+//! }
+//! assert_eq!(vec![0x07], values);
+//! ```
+
+use crate::{PackedStructSlice, PackingError, PackingResult};
+
+/// Decodes statically sized frames from a stream of bytes. `N` is the capacity of
+/// the internal buffer, which has to fit at least a single frame.
+pub struct PackedStreamDecoder<T, const N: usize> {
+    buffer: [u8; N],
+    /// The buffered bytes are `buffer[start..end]`. Consumed bytes only move `start`,
+    /// the rest is moved to the front once the buffer's end is reached.
+    start: usize,
+    end: usize,
+    frame_size: usize,
+    sync: &'static [u8],
+    checksum: Option<fn(&[u8]) -> bool>,
+    validate: Option<fn(&T) -> bool>
+}
+
+impl<T, const N: usize> PackedStreamDecoder<T, N> where T: PackedStructSlice {
+    /// A decoder that treats every byte as a potential start of the frame.
+    pub fn new() -> PackingResult<Self> {
+        let frame_size = T::packed_bytes_size(None)?;
+        if frame_size == 0 || frame_size > N {
+            return Err(PackingError::BufferTooSmall);
+        }
+
+        Ok(PackedStreamDecoder {
+            buffer: [0; N],
+            start: 0,
+            end: 0,
+            frame_size,
+            sync: &[],
+            checksum: None,
+            validate: None
+        })
+    }
+
+    /// Frames have to start with this sync pattern. Bytes in between are discarded.
+    pub fn with_sync(mut self, sync: &'static [u8]) -> Self {
+        self.sync = sync;
+        self
+    }
+
+    /// Validates the raw bytes of the frame before it is unpacked. Failures are
+    /// reported as `PackingError::ChecksumMismatch`.
+    pub fn with_checksum(mut self, checksum: fn(&[u8]) -> bool) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Checks the unpacked frame, for example for a header field's value. Frames that
+    /// aren't accepted are skipped silently, like bytes that don't match the sync pattern.
+    pub fn with_validation(mut self, validate: fn(&T) -> bool) -> Self {
+        self.validate = Some(validate);
+        self
+    }
+
+    /// Size of a single frame, in bytes.
+    pub fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    /// Number of bytes waiting for the rest of their frame.
+    pub fn buffered(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Discards all of the buffered bytes.
+    pub fn reset(&mut self) {
+        self.start = 0;
+        self.end = 0;
+    }
+
+    /// Feeds a chunk of the stream into the decoder. The returned iterator yields the
+    /// frames as the chunk is consumed, bytes of an incomplete frame stay buffered
+    /// for the next call.
+    ///
+    /// The chunk is read lazily, as the iterator advances. If the iterator is dropped
+    /// before it returns `None`, the unread part of the chunk is not buffered, see
+    /// [`PackedStreamFrames::remaining`].
+    #[must_use = "the chunk is only decoded while the returned iterator is advanced"]
+    pub fn feed<'a, 'b>(&'a mut self, chunk: &'b [u8]) -> PackedStreamFrames<'a, 'b, T, N> {
+        PackedStreamFrames {
+            decoder: self,
+            input: chunk
+        }
+    }
+
+    fn consume(&mut self, n: usize) {
+        self.start += n;
+        if self.start == self.end {
+            self.reset();
+        }
+    }
+
+    fn fill(&mut self, input: &mut &[u8]) {
+        if self.end == N {
+            self.buffer.copy_within(self.start..self.end, 0);
+            self.end -= self.start;
+            self.start = 0;
+        }
+
+        let n = ::core::cmp::min(N - self.end, input.len());
+        self.buffer[self.end..(self.end + n)].copy_from_slice(&input[..n]);
+        self.end += n;
+        *input = &input[n..];
+    }
+
+    /// Drops everything in front of the first, possibly partial, sync pattern.
+    fn align_to_sync(&mut self) {
+        if self.sync.is_empty() {
+            return;
+        }
+
+        let buffered = &self.buffer[self.start..self.end];
+        let start = (0..buffered.len())
+            .find(|&i| {
+                let candidate = &buffered[i..];
+                let l = ::core::cmp::min(candidate.len(), self.sync.len());
+                candidate[..l] == self.sync[..l]
+            })
+            .unwrap_or(buffered.len());
+
+        self.consume(start);
+    }
+
+    fn decode_buffered(&mut self) -> Option<PackingResult<T>> {
+        loop {
+            self.align_to_sync();
+            if self.buffered() < self.frame_size {
+                return None;
+            }
+
+            let frame = &self.buffer[self.start..(self.start + self.frame_size)];
+            if let Some(checksum) = self.checksum {
+                if !checksum(frame) {
+                    self.consume(1);
+                    return Some(Err(PackingError::ChecksumMismatch));
+                }
+            }
+
+            match T::unpack_from_slice(frame) {
+                Ok(unpacked) => {
+                    if self.validate.map(|v| v(&unpacked)).unwrap_or(true) {
+                        self.consume(self.frame_size);
+                        return Some(Ok(unpacked));
+                    }
+                    self.consume(1);
+                },
+                Err(e) => {
+                    self.consume(1);
+                    return Some(Err(e));
+                }
+            }
+        }
+    }
+}
+
+/// Frames decoded from a single chunk of the stream.
+#[must_use = "the chunk is only decoded while the iterator is advanced"]
+pub struct PackedStreamFrames<'a, 'b, T, const N: usize> {
+    decoder: &'a mut PackedStreamDecoder<T, N>,
+    input: &'b [u8]
+}
+
+impl<'a, 'b, T, const N: usize> PackedStreamFrames<'a, 'b, T, N> {
+    /// The part of the chunk that wasn't buffered yet. It has to be fed again if the
+    /// iterator is dropped before it's exhausted.
+    pub fn remaining(&self) -> &'b [u8] {
+        self.input
+    }
+}
+
+impl<'a, 'b, T, const N: usize> Iterator for PackedStreamFrames<'a, 'b, T, N> where T: PackedStructSlice {
+    type Item = PackingResult<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(frame) = self.decoder.decode_buffered() {
+                return Some(frame);
+            }
+            if self.input.is_empty() {
+                return None;
+            }
+            self.decoder.fill(&mut self.input);
+        }
+    }
+}
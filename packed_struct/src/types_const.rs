@@ -0,0 +1,161 @@
+//! Constant fields, like magic numbers, sync bytes or protocol versions.
+//!
+//! Unlike the reserved bits, the unpacked bits are checked. A different value fails
+//! with `PackingError::ConstMismatch`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use packed_struct::prelude::*;
+//!
+//! #[derive(PackedStruct, Debug, Default, PartialEq)]
+//! #[packed_struct(bit_numbering="msb0")]
+//! pub struct Header {
+//!     #[packed_field(bits="0..=15")]
+//!     magic: Const<packed_bits::Bits::<16>, 0xCAFE>,
+//!     #[packed_field(bits="16..=19")]
+//!     version: Const<packed_bits::Bits::<4>, 2>,
+//!     #[packed_field(bits="20..=23")]
+//!     flags: Integer<u8, packed_bits::Bits::<4>>
+//! }
+//!
+//! let header = Header { flags: 5.into(), ..Default::default() };
+//! assert_eq!([0xCA, 0xFE, 0x25], header.pack().unwrap());
+//!
+//! assert_eq!(Err(PackingError::ConstMismatch { expected: 0xCAFE, actual: 0xCAFF }), Header::unpack(&[0xCA, 0xFF, 0x25]));
+//! ```
+
+use crate::internal_prelude::v1::*;
+
+use crate::packing::*;
+use crate::types_bits::{ByteArray, NumberOfBits, NumberOfBytes};
+use crate::types_num::{lsb_align_bits, lsb_unalign_bits};
+
+/// A constant packed with the most significant byte first.
+pub type Const<B, const VALUE: u64> = ConstBits<ConstMsbFirst, B, VALUE>;
+
+/// A constant packed with the least significant byte first.
+pub type ConstLsb<B, const VALUE: u64> = ConstBits<ConstLsbFirst, B, VALUE>;
+
+/// The byte order of a packed constant.
+pub trait ConstByteOrder {
+    const MSB_FIRST: bool;
+}
+
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ConstMsbFirst;
+impl ConstByteOrder for ConstMsbFirst {
+    const MSB_FIRST: bool = true;
+}
+
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ConstLsbFirst;
+impl ConstByteOrder for ConstLsbFirst {
+    const MSB_FIRST: bool = false;
+}
+
+/// Always packs into `VALUE`, which has to fit into the bits `B`. Fails to unpack
+/// any other value. Doesn't hold any data, so it is created with `Default`.
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ConstBits<E, B, const VALUE: u64> {
+    order: PhantomData<E>,
+    bits: PhantomData<B>
+}
+
+impl<E, B, const VALUE: u64> Default for ConstBits<E, B, VALUE> {
+    fn default() -> Self {
+        ConstBits { order: PhantomData, bits: PhantomData }
+    }
+}
+
+impl<E, B, const VALUE: u64> ConstBits<E, B, VALUE> {
+    pub fn value(&self) -> u64 {
+        VALUE
+    }
+}
+
+impl<E, B, const VALUE: u64> Debug for ConstBits<E, B, VALUE> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Constant - always {:#X}", VALUE)
+    }
+}
+
+impl<E, B, const VALUE: u64> Display for ConstBits<E, B, VALUE> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Constant - always {:#X}", VALUE)
+    }
+}
+
+impl<E, B, const VALUE: u64> PackedStruct for ConstBits<E, B, VALUE> where E: ConstByteOrder, B: NumberOfBits {
+    type ByteArray = <<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes;
+
+    /// Fails with `PackingError::InvalidValue` if the constant doesn't fit into its bits.
+    fn pack(&self) -> PackingResult<Self::ByteArray> {
+        let bits = B::number_of_bits();
+        if bits < 64 && (VALUE >> bits) != 0 {
+            return Err(PackingError::InvalidValue);
+        }
+
+        let mut packed = Self::ByteArray::new(0);
+        let bytes = packed.as_mut_bytes_slice();
+        let len = bytes.len();
+        for (i, b) in bytes.iter_mut().enumerate() {
+            let shift = if E::MSB_FIRST { len - 1 - i } else { i };
+            *b = VALUE.checked_shr((shift * 8) as u32).unwrap_or(0) as u8;
+        }
+        if !E::MSB_FIRST {
+            lsb_align_bits(bytes, bits)?;
+        }
+        Ok(packed)
+    }
+
+    fn unpack(src: &Self::ByteArray) -> PackingResult<Self> {
+        let mut src = src.clone();
+        if !E::MSB_FIRST {
+            lsb_unalign_bits(src.as_mut_bytes_slice(), B::number_of_bits())?;
+        }
+        let bytes = src.as_bytes_slice();
+        let len = bytes.len();
+        let actual = bytes.iter().enumerate().fold(0, |v, (i, b)| {
+            let shift = if E::MSB_FIRST { len - 1 - i } else { i };
+            v | (*b as u64).checked_shl((shift * 8) as u32).unwrap_or(0)
+        });
+
+        if actual != VALUE {
+            return Err(PackingError::ConstMismatch { expected: VALUE, actual });
+        }
+        Ok(Self::default())
+    }
+}
+
+impl<E, B, const VALUE: u64> PackedStructInfo for ConstBits<E, B, VALUE> where B: NumberOfBits {
+    #[inline]
+    fn packed_bits() -> usize {
+        B::number_of_bits()
+    }
+}
+
+#[test]
+fn test_const_byte_order() {
+    use crate::types_bits::Bits;
+
+    assert_eq!([0x12, 0x34], <Const<Bits::<16>, 0x1234>>::default().pack().unwrap());
+    assert_eq!([0x34, 0x12], <ConstLsb<Bits::<16>, 0x1234>>::default().pack().unwrap());
+    assert_eq!(Err(PackingError::InvalidValue), <Const<Bits::<4>, 0x10>>::default().pack());
+    assert!(<ConstLsb<Bits::<16>, 0x1234>>::unpack(&[0x34, 0x12]).is_ok());
+    assert_eq!(Err(PackingError::ConstMismatch { expected: 0x1234, actual: 0x3412 }), <Const<Bits::<16>, 0x1234>>::unpack(&[0x34, 0x12]));
+}
+
+#[test]
+fn test_const_lsb_partial_bytes() {
+    use crate::types_bits::Bits;
+    use crate::types_num::{Integer, SizedInteger};
+
+    // aligned like the LSB integers
+    let packed = <ConstLsb<Bits::<12>, 0xABC>>::default().pack().unwrap();
+    assert_eq!(Integer::<u16, Bits::<12>>::from_primitive(0xABC).as_packed_lsb().pack().unwrap(), packed);
+    assert!(<ConstLsb<Bits::<12>, 0xABC>>::unpack(&packed).is_ok());
+}
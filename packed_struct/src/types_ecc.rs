@@ -0,0 +1,259 @@
+//! Error correcting Hamming codes for integer fields.
+//!
+//! Every nibble of the integer is encoded into its own codeword, the most significant
+//! one first. [`Hamming74`] corrects a single flipped bit per codeword. [`Secded`]
+//! adds an overall parity bit, so it also detects two flipped bits in a codeword and
+//! fails to unpack them with `PackingError::UncorrectableBitErrors`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use packed_struct::prelude::*;
+//!
+//! #[derive(PackedStruct, Debug, PartialEq)]
+//! pub struct Telemetry {
+//!     reading: Secded<u8>
+//! }
+//!
+//! let packed = Telemetry { reading: 0x5A.into() }.pack().unwrap();
+//! assert_eq!([0x4B, 0xB4], packed);
+//!
+//! // a bit flipped in transmission
+//! let unpacked = Telemetry::unpack(&[0x4B, 0xB5]).unwrap();
+//! assert_eq!(0x5A, *unpacked.reading);
+//! assert_eq!(1, unpacked.reading.corrected_bits());
+//!
+//! assert_eq!(Err(PackingError::UncorrectableBitErrors), Telemetry::unpack(&[0x4B, 0xB7]));
+//! ```
+
+use crate::internal_prelude::v1::*;
+
+use crate::{PackedStruct, PackedStructInfo, PackingError, PackingResult};
+
+/// Hamming(7,4), 7 bits for every 4 bits of the integer.
+pub type Hamming74<T> = Hamming<HammingCode74, T>;
+
+/// Hamming(8,4) with single error correction and double error detection, 8 bits for
+/// every 4 bits of the integer.
+pub type Secded<T> = Hamming<HammingCode84, T>;
+
+/// The codeword of a single nibble.
+pub trait HammingCode {
+    const CODEWORD_BITS: usize;
+    fn encode(nibble: u8) -> u8;
+    /// Returns the nibble and whether a bit had to be corrected.
+    fn decode(codeword: u8) -> PackingResult<(u8, bool)>;
+}
+
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HammingCode74;
+impl HammingCode for HammingCode74 {
+    const CODEWORD_BITS: usize = 7;
+
+    fn encode(nibble: u8) -> u8 {
+        let d = |i: u8| (nibble >> (3 - i)) & 1;
+        let p1 = d(0) ^ d(1) ^ d(3);
+        let p2 = d(0) ^ d(2) ^ d(3);
+        let p3 = d(1) ^ d(2) ^ d(3);
+        // positions 1 to 7, the first one is the most significant bit
+        [p1, p2, d(0), p3, d(1), d(2), d(3)].iter().fold(0, |c, b| (c << 1) | b)
+    }
+
+    fn decode(codeword: u8) -> PackingResult<(u8, bool)> {
+        let c = |pos: u8| (codeword >> (7 - pos)) & 1;
+        let syndrome = (c(1) ^ c(3) ^ c(5) ^ c(7)) |
+                       (c(2) ^ c(3) ^ c(6) ^ c(7)) << 1 |
+                       (c(4) ^ c(5) ^ c(6) ^ c(7)) << 2;
+
+        let codeword = if syndrome != 0 { codeword ^ (1 << (7 - syndrome)) } else { codeword };
+        let c = |pos: u8| (codeword >> (7 - pos)) & 1;
+        Ok(((c(3) << 3) | (c(5) << 2) | (c(6) << 1) | c(7), syndrome != 0))
+    }
+}
+
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct HammingCode84;
+impl HammingCode for HammingCode84 {
+    const CODEWORD_BITS: usize = 8;
+
+    /// The Hamming(7,4) codeword, followed by the even parity of all of its bits.
+    fn encode(nibble: u8) -> u8 {
+        let codeword = HammingCode74::encode(nibble);
+        (codeword << 1) | (codeword.count_ones() % 2) as u8
+    }
+
+    fn decode(codeword: u8) -> PackingResult<(u8, bool)> {
+        let parity_error = codeword.count_ones() % 2 != 0;
+        let (nibble, corrected) = HammingCode74::decode(codeword >> 1)?;
+
+        match (parity_error, corrected) {
+            (false, false) => Ok((nibble, false)),
+            // either a data bit or the parity bit itself
+            (true, _) => Ok((nibble, true)),
+            (false, true) => Err(PackingError::UncorrectableBitErrors)
+        }
+    }
+}
+
+/// An integer that is encoded with a Hamming code. Remembers how many bits had to be
+/// corrected while it was unpacked.
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[derive(Default, Copy, Clone)]
+pub struct Hamming<C, T> {
+    value: T,
+    corrected_bits: usize,
+    code: PhantomData<C>
+}
+
+impl<C, T> Hamming<C, T> {
+    pub fn new(value: T) -> Self {
+        Hamming { value, corrected_bits: 0, code: PhantomData }
+    }
+
+    /// Number of flipped bits that were corrected when unpacking. At most one for
+    /// every codeword.
+    pub fn corrected_bits(&self) -> usize {
+        self.corrected_bits
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<C, T> From<T> for Hamming<C, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<C, T> Deref for Hamming<C, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Compares the values only.
+impl<C, T> PartialEq for Hamming<C, T> where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<C, T> Debug for Hamming<C, T> where T: Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.corrected_bits > 0 {
+            write!(f, "{:?} ({} corrected bits)", self.value, self.corrected_bits)
+        } else {
+            write!(f, "{:?}", self.value)
+        }
+    }
+}
+
+impl<C, T> Display for Hamming<C, T> where T: Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+macro_rules! hamming_impl {
+    ($C: ty, $T: ty, $N: expr) => {
+        impl PackedStruct for Hamming<$C, $T> {
+            type ByteArray = [u8; $N];
+
+            fn pack(&self) -> PackingResult<[u8; $N]> {
+                const NIBBLES: usize = mem::size_of::<$T>() * 2;
+
+                // the codewords are aligned to the least significant bit
+                let mut codewords = 0u128;
+                for i in (0..NIBBLES).rev() {
+                    let nibble = ((self.value >> (i * 4)) & 0xF) as u8;
+                    codewords = (codewords << <$C>::CODEWORD_BITS) | <$C>::encode(nibble) as u128;
+                }
+
+                let mut packed = [0; $N];
+                packed.copy_from_slice(&codewords.to_be_bytes()[(16 - $N)..]);
+                Ok(packed)
+            }
+
+            fn unpack(src: &[u8; $N]) -> PackingResult<Self> {
+                const NIBBLES: usize = mem::size_of::<$T>() * 2;
+
+                let mut bytes = [0; 16];
+                bytes[(16 - $N)..].copy_from_slice(src);
+                let codewords = u128::from_be_bytes(bytes);
+                let mask = (1u128 << <$C>::CODEWORD_BITS) - 1;
+
+                let mut value = 0;
+                let mut corrected_bits = 0;
+                for i in (0..NIBBLES).rev() {
+                    let codeword = (codewords >> (i * <$C>::CODEWORD_BITS)) & mask;
+                    let (nibble, corrected) = <$C>::decode(codeword as u8)?;
+                    value = (value << 4) | nibble as $T;
+                    if corrected {
+                        corrected_bits += 1;
+                    }
+                }
+
+                Ok(Hamming { value, corrected_bits, code: PhantomData })
+            }
+        }
+
+        impl PackedStructInfo for Hamming<$C, $T> {
+            #[inline]
+            fn packed_bits() -> usize {
+                mem::size_of::<$T>() * 2 * <$C>::CODEWORD_BITS
+            }
+        }
+    };
+}
+
+hamming_impl!(HammingCode74, u8, 2);
+hamming_impl!(HammingCode74, u16, 4);
+hamming_impl!(HammingCode74, u32, 7);
+hamming_impl!(HammingCode74, u64, 14);
+hamming_impl!(HammingCode84, u8, 2);
+hamming_impl!(HammingCode84, u16, 4);
+hamming_impl!(HammingCode84, u32, 8);
+hamming_impl!(HammingCode84, u64, 16);
+
+#[test]
+fn test_hamming_single_errors() {
+    for nibble in 0..16 {
+        let codeword = HammingCode74::encode(nibble);
+        assert_eq!(Ok((nibble, false)), HammingCode74::decode(codeword));
+        for bit in 0..7 {
+            assert_eq!(Ok((nibble, true)), HammingCode74::decode(codeword ^ (1 << bit)));
+        }
+
+        let codeword = HammingCode84::encode(nibble);
+        assert_eq!(Ok((nibble, false)), HammingCode84::decode(codeword));
+        for bit in 0..8 {
+            assert_eq!(Ok((nibble, true)), HammingCode84::decode(codeword ^ (1 << bit)));
+            for other in 0..bit {
+                assert_eq!(Err(PackingError::UncorrectableBitErrors), HammingCode84::decode(codeword ^ (1 << bit) ^ (1 << other)));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_hamming_integers() {
+    let value = <Hamming74<u32>>::new(0x1234_ABCD);
+    let mut packed = value.pack().unwrap();
+    packed[6] ^= 0x01;
+    packed[1] ^= 0x80;
+    let unpacked = <Hamming74<u32>>::unpack(&packed).unwrap();
+    assert_eq!(0x1234_ABCD, *unpacked);
+    assert_eq!(2, unpacked.corrected_bits());
+
+    // 14 bits, aligned to the least significant bit
+    assert_eq!(0, <Hamming74<u8>>::new(0xFF).pack().unwrap()[0] & 0xC0);
+
+    let value = <Secded<u64>>::new(0xFEDC_BA98_7654_3210);
+    assert_eq!(value, <Secded<u64>>::unpack(&value.pack().unwrap()).unwrap());
+}
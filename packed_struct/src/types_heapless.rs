@@ -0,0 +1,88 @@
+//! Fixed capacity `heapless` collections as dynamically sized structures
+
+use crate::{PackedStructSlice, PackingError, PackingResult, lib_get_mut_slice, lib_get_slice};
+
+/// A vector of structures that have a statically known size. Unpacking fails if the
+/// items don't fit into the vector's capacity.
+impl<T, const N: usize> PackedStructSlice for heapless::Vec<T, N> where T: PackedStructSlice {
+    fn pack_to_slice(&self, output: &mut [u8]) -> PackingResult<()> {
+        let expected_size = Self::packed_bytes_size(Some(self))?;
+        if output.len() != expected_size {
+            return Err(PackingError::BufferSizeMismatch { expected: expected_size, actual: output.len() });
+        }
+
+        let size = T::packed_bytes_size(None)?;
+
+        for (i, item) in self.iter().enumerate() {
+            let item_out = lib_get_mut_slice(output, (i * size)..((i+1)*size))?;
+            item.pack_to_slice(item_out)?;
+        }
+
+        Ok(())
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> PackingResult<Self> {
+        let item_size = T::packed_bytes_size(None)?;
+        if item_size == 0 || src.is_empty() { return Ok(heapless::Vec::new()); }
+        let modulo = src.len() % item_size;
+        if modulo != 0 {
+            return Err(PackingError::BufferModMismatch { actual_size: src.len(), modulo_required: item_size });
+        }
+        let n = src.len() / item_size;
+        if n > N {
+            return Err(PackingError::CapacityExceeded { capacity: N, required: n });
+        }
+
+        let mut vec = heapless::Vec::new();
+        for i in 0..n {
+            let item_src = lib_get_slice(src, (i*item_size)..((i+1)*item_size))?;
+            let item = T::unpack_from_slice(item_src)?;
+            vec.push(item).map_err(|_| PackingError::CapacityExceeded { capacity: N, required: n })?;
+        }
+
+        Ok(vec)
+    }
+
+    /// Unpacks as many whole items as the buffer holds, a trailing partial item is
+    /// returned as the remainder.
+    fn unpack_from_prefix(src: &[u8]) -> PackingResult<(Self, &[u8])> {
+        let item_size = T::packed_bytes_size(None)?;
+        if item_size == 0 { return Ok((heapless::Vec::new(), src)); }
+        let (items, rest) = src.split_at(src.len() - (src.len() % item_size));
+        Ok((Self::unpack_from_slice(items)?, rest))
+    }
+
+    fn packed_bytes_size(opt_self: Option<&Self>) -> PackingResult<usize> {
+        match opt_self {
+            None => Err(PackingError::InstanceRequiredForSize),
+            Some(s) => Ok(s.len() * T::packed_bytes_size(None)?)
+        }
+    }
+}
+
+/// The string's UTF-8 bytes. Unpacking fails on invalid UTF-8 or if the string doesn't
+/// fit into the capacity.
+impl<const N: usize> PackedStructSlice for heapless::String<N> {
+    fn pack_to_slice(&self, output: &mut [u8]) -> PackingResult<()> {
+        if output.len() != self.len() {
+            return Err(PackingError::BufferSizeMismatch { expected: self.len(), actual: output.len() });
+        }
+        output.copy_from_slice(self.as_bytes());
+        Ok(())
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> PackingResult<Self> {
+        if src.len() > N {
+            return Err(PackingError::CapacityExceeded { capacity: N, required: src.len() });
+        }
+
+        let s = core::str::from_utf8(src).map_err(|_| PackingError::InvalidValue)?;
+        let mut string = heapless::String::new();
+        string.push_str(s).map_err(|_| PackingError::CapacityExceeded { capacity: N, required: src.len() })?;
+        Ok(string)
+    }
+
+    fn packed_bytes_size(opt_self: Option<&Self>) -> PackingResult<usize> {
+        opt_self.map(|s| s.len()).ok_or(PackingError::InstanceRequiredForSize)
+    }
+}
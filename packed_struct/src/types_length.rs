@@ -0,0 +1,243 @@
+//! Integers that store the length of a dynamically sized payload.
+//!
+//! [`LengthPrefixed`] packs such a length in front of its payload, so any number of
+//! dynamically sized members can be chained in a tuple, up to the tuple's limit of
+//! 24 members.
+//!
+//! # Example
+//!
+//! ```rust
+//! use packed_struct::prelude::*;
+//!
+//! type Length = MsbInteger<u16, packed_bits::Bits::<16>, Integer<u16, packed_bits::Bits::<16>>>;
+//! type Message = (u8, LengthPrefixed<u8, Vec<u8>>, u8, LengthPrefixed<Length, Vec<u8>>, Vec<u8>);
+//!
+//! let raw = [0x10, 2, 0xA1, 0xA2, 0x20, 0x00, 0x01, 0xB1, 0xC1, 0xC2];
+//! let unpacked = Message::unpack_from_slice(&raw).unwrap();
+//! assert_eq!(&[0xA1, 0xA2], &unpacked.1[..]);
+//! assert_eq!(0x20, unpacked.2);
+//! assert_eq!(&[0xB1], &unpacked.3[..]);
+//! assert_eq!(&[0xC1, 0xC2], &unpacked.4[..]);
+//! let packed = unpacked.pack_to_vec().unwrap();
+//! assert_eq!(&raw[..], &packed[..]);
+//! ```
+
+use core::convert::{TryFrom, TryInto};
+use core::ops::DerefMut;
+
+use crate::internal_prelude::v1::*;
+
+use crate::{PackedStruct, PackedStructSlice, PackingError, PackingResult};
+use crate::types_bits::{ByteArray, NumberOfBits};
+use crate::types_num::{Integer, LsbInteger, MsbInteger, SizedInteger};
+
+/// A field value that counts the elements of a dynamically sized field.
+pub trait ElementCount: Sized {
+    /// Converts the count into the field's type. Fails if it doesn't fit.
+    fn from_count(count: usize) -> PackingResult<Self>;
+    /// The count stored in this field.
+    fn to_count(&self) -> PackingResult<usize>;
+}
+
+macro_rules! element_count_impl {
+    ($T: ty) => {
+        impl ElementCount for $T {
+            fn from_count(count: usize) -> PackingResult<Self> {
+                <$T>::try_from(count).map_err(|_| PackingError::InvalidValue)
+            }
+
+            fn to_count(&self) -> PackingResult<usize> {
+                (*self).try_into().map_err(|_| PackingError::InvalidValue)
+            }
+        }
+    };
+}
+
+element_count_impl!(u8);
+element_count_impl!(u16);
+element_count_impl!(u32);
+element_count_impl!(u64);
+
+impl<T, B> ElementCount for Integer<T, B>
+    where T: ElementCount + Copy + PartialEq, B: NumberOfBits, Integer<T, B>: SizedInteger<T, B>
+{
+    fn from_count(count: usize) -> PackingResult<Self> {
+        let n = T::from_count(count)?;
+        let i = Self::from_primitive(n);
+        if i.to_primitive() != n {
+            return Err(PackingError::InvalidValue);
+        }
+        Ok(i)
+    }
+
+    fn to_count(&self) -> PackingResult<usize> {
+        self.to_primitive().to_count()
+    }
+}
+
+/// A packed integer that describes the number of bytes that follow it.
+pub trait PackedLength: PackedStruct {
+    /// Converts the length into the packed integer. Fails if it doesn't fit into its bits.
+    fn from_length(len: usize) -> PackingResult<Self>;
+    /// The length stored in this packed integer.
+    fn to_length(&self) -> PackingResult<usize>;
+}
+
+impl PackedLength for u8 {
+    fn from_length(len: usize) -> PackingResult<Self> {
+        u8::from_count(len)
+    }
+
+    fn to_length(&self) -> PackingResult<usize> {
+        self.to_count()
+    }
+}
+
+impl<T, B> PackedLength for MsbInteger<T, B, Integer<T, B>>
+    where B: NumberOfBits, Integer<T, B>: SizedInteger<T, B> + ElementCount
+{
+    fn from_length(len: usize) -> PackingResult<Self> {
+        <Integer<T, B>>::from_count(len).map(Into::into)
+    }
+
+    fn to_length(&self) -> PackingResult<usize> {
+        (**self).to_count()
+    }
+}
+
+impl<T, B> PackedLength for LsbInteger<T, B, Integer<T, B>>
+    where B: NumberOfBits, Integer<T, B>: SizedInteger<T, B> + ElementCount
+{
+    fn from_length(len: usize) -> PackingResult<Self> {
+        <Integer<T, B>>::from_count(len).map(Into::into)
+    }
+
+    fn to_length(&self) -> PackingResult<usize> {
+        (**self).to_count()
+    }
+}
+
+/// A dynamically sized structure, packed behind its length in bytes. The prefix `L`
+/// is a packed integer, for example a `u8` or a `MsbInteger`.
+pub struct LengthPrefixed<L, T> {
+    value: T,
+    prefix: PhantomData<L>
+}
+
+// manual impls, the prefix type doesn't have to implement these traits
+impl<L, T> Debug for LengthPrefixed<L, T> where T: Debug {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("LengthPrefixed").field(&self.value).finish()
+    }
+}
+
+impl<L, T> Default for LengthPrefixed<L, T> where T: Default {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<L, T> Clone for LengthPrefixed<L, T> where T: Clone {
+    fn clone(&self) -> Self {
+        Self::new(self.value.clone())
+    }
+}
+
+impl<L, T> Copy for LengthPrefixed<L, T> where T: Copy { }
+
+impl<L, T> PartialEq for LengthPrefixed<L, T> where T: PartialEq {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<L, T> LengthPrefixed<L, T> {
+    pub fn new(value: T) -> Self {
+        LengthPrefixed { value, prefix: PhantomData }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<L, T> From<T> for LengthPrefixed<L, T> {
+    fn from(value: T) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<L, T> Deref for LengthPrefixed<L, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<L, T> DerefMut for LengthPrefixed<L, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<L, T> PackedStructSlice for LengthPrefixed<L, T> where L: PackedLength, T: PackedStructSlice {
+    fn pack_to_slice(&self, output: &mut [u8]) -> PackingResult<()> {
+        let prefix_len = <L::ByteArray as ByteArray>::len();
+        let length = T::packed_bytes_size(Some(&self.value))?;
+        let expected = prefix_len + length;
+        if output.len() != expected {
+            return Err(PackingError::BufferSizeMismatch { expected, actual: output.len() });
+        }
+
+        let (prefix, payload) = output.split_at_mut(prefix_len);
+        prefix.copy_from_slice(L::from_length(length)?.pack()?.as_bytes_slice());
+        self.value.pack_to_slice(payload)
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> PackingResult<Self> {
+        let expected = Self::packed_bytes_size_from_prefix(src)?;
+        if src.len() != expected {
+            return Err(PackingError::BufferSizeMismatch { expected, actual: src.len() });
+        }
+
+        let prefix_len = <L::ByteArray as ByteArray>::len();
+        Ok(Self::new(T::unpack_from_slice(&src[prefix_len..])?))
+    }
+
+    fn packed_bytes_size(opt_self: Option<&Self>) -> PackingResult<usize> {
+        let s = opt_self.ok_or(PackingError::InstanceRequiredForSize)?;
+        Ok(<L::ByteArray as ByteArray>::len() + T::packed_bytes_size(Some(&s.value))?)
+    }
+
+    fn packed_bytes_size_from_prefix(src: &[u8]) -> PackingResult<usize> {
+        let prefix_len = <L::ByteArray as ByteArray>::len();
+        if src.len() < prefix_len {
+            return Err(PackingError::BufferTooSmall);
+        }
+
+        let length = L::unpack_from_slice(&src[..prefix_len])?.to_length()?;
+        length.checked_add(prefix_len).ok_or(PackingError::InvalidValue)
+    }
+}
+
+#[test]
+fn test_packed_length() {
+    use crate::types_bits::Bits;
+
+    type Len12 = MsbInteger<u16, Bits::<12>, Integer<u16, Bits::<12>>>;
+
+    assert_eq!(4095, Len12::from_length(4095).unwrap().to_length().unwrap());
+    assert_eq!(Err(PackingError::InvalidValue), Len12::from_length(4096).map(|_| ()));
+    assert_eq!(Err(PackingError::InvalidValue), u8::from_length(256));
+    assert_eq!([0x01, 0x02], Len12::from_length(0x102).unwrap().pack().unwrap());
+}
+
+#[test]
+fn test_element_count() {
+    use crate::types_bits::Bits;
+
+    assert_eq!(Ok(7), <Integer<u8, Bits::<3>>>::from_count(7).map(|i| *i));
+    assert_eq!(Err(PackingError::InvalidValue), <Integer<u8, Bits::<3>>>::from_count(8).map(|_| ()));
+    assert_eq!(Ok(1000), 1000u16.to_count());
+}
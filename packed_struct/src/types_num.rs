@@ -660,46 +660,15 @@ impl<T, B, I> PackedStruct for LsbInteger<T, B, I>
 
     fn pack(&self) -> PackingResult<<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes> {
         let mut bytes = self.0.to_lsb_bytes()?;
-        
-        let l = B::byte_array_len() * 8;
-        let shift_by_bits = l - B::number_of_bits();
-        if shift_by_bits > 0 && (shift_by_bits % 8) != 0 {
-            use bitvec::prelude::*;
-
-            let leftover_bits = B::number_of_bits() % 8;            
-            
-            let bytes_slice = bytes.as_mut_bytes_slice();
-            let bits = BitSlice::<_, Msb0>::try_from_slice_mut(bytes_slice).map_err(|_| PackingError::BitsError)?;
-            let s = l - B::number_of_bits();
-            let (left, _) = bits.split_at_mut(l - leftover_bits);
-            left.shift_right(s);
-        }
-        
+        lsb_align_bits(bytes.as_mut_bytes_slice(), B::number_of_bits())?;
         Ok(bytes)
     }
 
     #[inline]
-    fn unpack(src: &<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes) -> PackingResult<Self> {        
-        let l = B::byte_array_len() * 8;
-        let shift_by_bits = l - B::number_of_bits();
-
-        let n = if shift_by_bits > 0 && (shift_by_bits % 8) != 0 {
-            use bitvec::prelude::*;
-
-            let leftover_bits = B::number_of_bits() % 8;
-
-            let mut src_bytes = (*src).clone();
-            let bytes_slice = src_bytes.as_mut_bytes_slice();
-            let bits = BitSlice::<_, Msb0>::try_from_slice_mut(bytes_slice).map_err(|_| PackingError::BitsError)?;
-            let s = l - B::number_of_bits();
-            let (left, _) = bits.split_at_mut(l - leftover_bits);
-            left.shift_left(s);
-
-            I::from_lsb_bytes(&src_bytes)?
-        } else {
-            I::from_lsb_bytes(src)?
-        };
-        
+    fn unpack(src: &<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes) -> PackingResult<Self> {
+        let mut src_bytes = (*src).clone();
+        lsb_unalign_bits(src_bytes.as_mut_bytes_slice(), B::number_of_bits())?;
+        let n = I::from_lsb_bytes(&src_bytes)?;
         let n = LsbInteger(n, Default::default(), Default::default());
         Ok(n)
     }
@@ -712,6 +681,42 @@ impl<T, B, I> PackedStructInfo for LsbInteger<T, B, I> where B: NumberOfBits {
     }
 }
 
+/// Shifts the full bytes of a LSB byte array into the empty bits of its non-full byte.
+pub(crate) fn lsb_align_bits(bytes: &mut [u8], number_of_bits: usize) -> PackingResult<()> {
+    let l = bytes.len() * 8;
+    let shift_by_bits = l - number_of_bits;
+    if shift_by_bits > 0 && (shift_by_bits % 8) != 0 {
+        use bitvec::prelude::*;
+
+        let leftover_bits = number_of_bits % 8;
+
+        let bits = BitSlice::<_, Msb0>::try_from_slice_mut(bytes).map_err(|_| PackingError::BitsError)?;
+        let (left, _) = bits.split_at_mut(l - leftover_bits);
+        #[allow(deprecated)]
+        left.shift_right(shift_by_bits);
+    }
+
+    Ok(())
+}
+
+/// Reverses `lsb_align_bits`.
+pub(crate) fn lsb_unalign_bits(bytes: &mut [u8], number_of_bits: usize) -> PackingResult<()> {
+    let l = bytes.len() * 8;
+    let shift_by_bits = l - number_of_bits;
+    if shift_by_bits > 0 && (shift_by_bits % 8) != 0 {
+        use bitvec::prelude::*;
+
+        let leftover_bits = number_of_bits % 8;
+
+        let bits = BitSlice::<_, Msb0>::try_from_slice_mut(bytes).map_err(|_| PackingError::BitsError)?;
+        let (left, _) = bits.split_at_mut(l - leftover_bits);
+        #[allow(deprecated)]
+        left.shift_left(shift_by_bits);
+    }
+
+    Ok(())
+}
+
 
 #[test]
 fn test_packed_int_msb() {
@@ -1,4 +1,8 @@
 //! Reserved space in a packed structure, either just zeroes or ones.
+//!
+//! The strict variants fail to unpack if the bits aren't set as expected, which helps
+//! to catch misaligned parsing and corrupted data. `ReservedPreserve` keeps whatever
+//! was unpacked and packs it back unchanged.
 
 use crate::internal_prelude::v1::*;
 
@@ -10,6 +14,14 @@ pub type ReservedZeroes<B> = ReservedZero<B>;
 pub type ReservedOne<B> = ReservedBits<BitOne, B>;
 pub type ReservedOnes<B> = ReservedOne<B>;
 
+/// Packs into a set of zeroes. Fails with `PackingError::ReservedBitsMismatch` when
+/// unpacking anything else.
+pub type ReservedZeroStrict<B> = ReservedBitsStrict<BitZero, B>;
+
+/// Packs into a set of ones. Fails with `PackingError::ReservedBitsMismatch` when
+/// unpacking anything else.
+pub type ReservedOneStrict<B> = ReservedBitsStrict<BitOne, B>;
+
 pub trait ReservedBitValue {
     fn get_reserved_bit_value_byte() -> u8;
 }
@@ -85,4 +97,152 @@ impl<V, B> PackedStructInfo for ReservedBits<V, B> where B: NumberOfBits {
     fn packed_bits() -> usize {
         B::number_of_bits()
     }
-}
\ No newline at end of file
+}
+
+/// Always packs into the associated bit value. Fails to unpack different bits.
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[derive(Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ReservedBitsStrict<V, B> {
+    value: V,
+    bits: PhantomData<B>
+}
+
+impl<B> Debug for ReservedBitsStrict<BitZero, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Reserved - always 0")
+    }
+}
+
+impl<B> Display for ReservedBitsStrict<BitZero, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Reserved - always 0")
+    }
+}
+
+impl<B> Debug for ReservedBitsStrict<BitOne, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Reserved - always 1")
+    }
+}
+
+impl<B> Display for ReservedBitsStrict<BitOne, B> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Reserved - always 1")
+    }
+}
+
+/// Keeps the unpacked bits and packs them back unchanged. Packs into zeroes when
+/// created with `Default`.
+#[cfg_attr(feature = "use_serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "use_serde", serde(bound(
+    serialize = "<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes: serde::Serialize",
+    deserialize = "<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes: serde::Deserialize<'de>"
+)))]
+pub struct ReservedPreserve<B> where B: NumberOfBits {
+    raw: <<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes
+}
+
+impl<B> ReservedPreserve<B> where B: NumberOfBits {
+    pub fn new(raw: <<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes) -> Self {
+        ReservedPreserve { raw }
+    }
+
+    /// The preserved bits, aligned to the least significant bit.
+    pub fn raw(&self) -> &<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes {
+        &self.raw
+    }
+}
+
+impl<B> Default for ReservedPreserve<B> where B: NumberOfBits {
+    fn default() -> Self {
+        Self::new(ByteArray::new(0))
+    }
+}
+
+impl<B> Clone for ReservedPreserve<B> where B: NumberOfBits {
+    fn clone(&self) -> Self {
+        Self::new(self.raw.clone())
+    }
+}
+
+impl<B> Copy for ReservedPreserve<B> where B: NumberOfBits, <<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes: Copy { }
+
+impl<B> PartialEq for ReservedPreserve<B> where B: NumberOfBits {
+    fn eq(&self, other: &Self) -> bool {
+        self.raw.as_bytes_slice() == other.raw.as_bytes_slice()
+    }
+}
+
+impl<B> Eq for ReservedPreserve<B> where B: NumberOfBits { }
+
+impl<B> Debug for ReservedPreserve<B> where B: NumberOfBits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Reserved - preserved {:?}", self.raw.as_bytes_slice())
+    }
+}
+
+impl<B> Display for ReservedPreserve<B> where B: NumberOfBits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Reserved - preserved {:?}", self.raw.as_bytes_slice())
+    }
+}
+
+impl<V, B> PackedStruct for ReservedBitsStrict<V, B> where Self: Default, V: ReservedBitValue, B: NumberOfBits {
+    type ByteArray = <<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes;
+    fn pack(&self) -> PackingResult<<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes> {
+        Ok(<<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes>::new(V::get_reserved_bit_value_byte()))
+    }
+
+    fn unpack(src: &<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes) -> Result<Self, PackingError> {
+        let value = V::get_reserved_bit_value_byte();
+        // the unused high bits of the first byte are ignored
+        let partial = B::number_of_bits() % 8;
+        let first_mask = if partial == 0 { 0xFF } else { (1u8 << partial) - 1 };
+
+        let matches = src.as_bytes_slice().iter().enumerate().all(|(i, b)| {
+            let mask = if i == 0 { first_mask } else { 0xFF };
+            (b & mask) == (value & mask)
+        });
+
+        if !matches {
+            return Err(PackingError::ReservedBitsMismatch);
+        }
+        Ok(Self::default())
+    }
+}
+
+impl<V, B> PackedStructInfo for ReservedBitsStrict<V, B> where B: NumberOfBits {
+    #[inline]
+    fn packed_bits() -> usize {
+        B::number_of_bits()
+    }
+}
+
+impl<B> PackedStruct for ReservedPreserve<B> where B: NumberOfBits {
+    type ByteArray = <<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes;
+    fn pack(&self) -> PackingResult<<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes> {
+        Ok(self.raw.clone())
+    }
+
+    fn unpack(src: &<<B as NumberOfBits>::Bytes as NumberOfBytes>::AsBytes) -> Result<Self, PackingError> {
+        Ok(Self::new(src.clone()))
+    }
+}
+
+impl<B> PackedStructInfo for ReservedPreserve<B> where B: NumberOfBits {
+    #[inline]
+    fn packed_bits() -> usize {
+        B::number_of_bits()
+    }
+}
+
+#[test]
+fn test_reserved_strict() {
+    use crate::types_bits::Bits;
+
+    assert!(<ReservedZeroStrict<Bits::<4>>>::unpack(&[0]).is_ok());
+    assert!(<ReservedOneStrict<Bits::<4>>>::unpack(&[0x0F]).is_ok());
+    assert!(<ReservedOneStrict<Bits::<12>>>::unpack(&[0x0F, 0xFF]).is_ok());
+    assert_eq!(Err(PackingError::ReservedBitsMismatch), <ReservedZeroStrict<Bits::<4>>>::unpack(&[0x01]));
+    assert_eq!(Err(PackingError::ReservedBitsMismatch), <ReservedOneStrict<Bits::<12>>>::unpack(&[0x0F, 0xFE]));
+}
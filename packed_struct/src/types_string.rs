@@ -0,0 +1,318 @@
+//! Fixed length strings, padded to their full size.
+//!
+//! The padding, the character encoding and the handling of strings that are too long
+//! are selected with marker types, like the bit values of the reserved types.
+//!
+//! # Example
+//!
+//! ```rust
+//! use packed_struct::prelude::*;
+//!
+//! #[derive(PackedStruct, Debug, PartialEq)]
+//! pub struct DeviceInfo {
+//!     name: FixedString<8>,
+//!     serial: PackedString<SpacePadded, Ascii, TruncateOverflow, 4>
+//! }
+//!
+//! let info = DeviceInfo {
+//!     name: FixedString::new("sensor").unwrap(),
+//!     serial: PackedString::new("AB12345").unwrap()
+//! };
+//!
+//! let packed = info.pack().unwrap();
+//! assert_eq!(b"sensor\0\0AB12", &packed);
+//!
+//! let unpacked = DeviceInfo::unpack(&packed).unwrap();
+//! assert_eq!("sensor", unpacked.name);
+//! assert_eq!("AB12", unpacked.serial);
+//! ```
+
+use core::convert::TryFrom;
+
+use crate::internal_prelude::v1::*;
+
+use crate::{PackedStruct, PackingError, PackingResult};
+
+/// A null-padded, UTF-8 encoded string that refuses strings longer than `N` bytes.
+pub type FixedString<const N: usize> = PackedString<NullPadded, Utf8, RejectOverflow, N>;
+
+/// The byte that fills the space after the string.
+pub trait StringPadding {
+    const PADDING: u8;
+}
+
+/// Padded with zeroes. The string ends at the first zero byte.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct NullPadded;
+impl StringPadding for NullPadded {
+    const PADDING: u8 = 0;
+}
+
+/// Padded with spaces. Trailing spaces are removed when unpacking.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SpacePadded;
+impl StringPadding for SpacePadded {
+    const PADDING: u8 = b' ';
+}
+
+/// The character encoding of the packed string.
+pub trait StringEncoding {
+    /// Encodes a single character into the buffer and returns the number of bytes used.
+    /// Fails if the encoding can't represent the character.
+    fn encode_char(c: char, buf: &mut [u8; 4]) -> Option<usize>;
+    /// Checks the unpacked bytes.
+    fn is_valid(bytes: &[u8]) -> bool;
+    /// Decodes the first character of valid bytes and returns its length in bytes.
+    fn decode_char(bytes: &[u8]) -> Option<(char, usize)>;
+}
+
+/// 7-bit ASCII.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Ascii;
+impl StringEncoding for Ascii {
+    fn encode_char(c: char, buf: &mut [u8; 4]) -> Option<usize> {
+        if !c.is_ascii() {
+            return None;
+        }
+        buf[0] = c as u8;
+        Some(1)
+    }
+
+    fn is_valid(bytes: &[u8]) -> bool {
+        bytes.is_ascii()
+    }
+
+    fn decode_char(bytes: &[u8]) -> Option<(char, usize)> {
+        bytes.first().map(|b| (char::from(*b), 1))
+    }
+}
+
+/// UTF-8, the encoding of Rust's strings.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Utf8;
+impl StringEncoding for Utf8 {
+    fn encode_char(c: char, buf: &mut [u8; 4]) -> Option<usize> {
+        Some(c.encode_utf8(buf).len())
+    }
+
+    fn is_valid(bytes: &[u8]) -> bool {
+        core::str::from_utf8(bytes).is_ok()
+    }
+
+    fn decode_char(bytes: &[u8]) -> Option<(char, usize)> {
+        let len = match bytes.first()? {
+            b if b & 0x80 == 0 => 1,
+            b if b & 0xE0 == 0xC0 => 2,
+            b if b & 0xF0 == 0xE0 => 3,
+            _ => 4
+        };
+        let c = core::str::from_utf8(bytes.get(..len)?).ok()?.chars().next()?;
+        Some((c, len))
+    }
+}
+
+/// ISO 8859-1, a single byte for each of the first 256 Unicode code points.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Latin1;
+impl StringEncoding for Latin1 {
+    fn encode_char(c: char, buf: &mut [u8; 4]) -> Option<usize> {
+        let c = c as u32;
+        if c > 0xFF {
+            return None;
+        }
+        buf[0] = c as u8;
+        Some(1)
+    }
+
+    fn is_valid(_bytes: &[u8]) -> bool {
+        true
+    }
+
+    fn decode_char(bytes: &[u8]) -> Option<(char, usize)> {
+        bytes.first().map(|b| (char::from(*b), 1))
+    }
+}
+
+/// What happens with strings that don't fit.
+pub trait StringOverflow {
+    const TRUNCATE: bool;
+}
+
+/// Cuts the string after the last character that fits.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TruncateOverflow;
+impl StringOverflow for TruncateOverflow {
+    const TRUNCATE: bool = true;
+}
+
+/// Fails with `PackingError::CapacityExceeded`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct RejectOverflow;
+impl StringOverflow for RejectOverflow {
+    const TRUNCATE: bool = false;
+}
+
+/// A string packed into exactly `N` bytes. Holds the encoded bytes, so it doesn't need
+/// an allocator.
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+pub struct PackedString<P, E, O, const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+    format: PhantomData<(P, E, O)>
+}
+
+impl<P, E, O, const N: usize> PackedString<P, E, O, N> where P: StringPadding, E: StringEncoding, O: StringOverflow {
+    /// Encodes the string. Fails with `PackingError::InvalidValue` if the encoding can't
+    /// represent one of the characters.
+    pub fn new(s: &str) -> PackingResult<Self> {
+        let mut r = Self::default();
+        let mut required = 0;
+        let mut buf = [0; 4];
+        for c in s.chars() {
+            let len = E::encode_char(c, &mut buf).ok_or(PackingError::InvalidValue)?;
+            // stop at the first character that doesn't fit
+            if required == r.len && r.len + len <= N {
+                r.bytes[r.len..(r.len + len)].copy_from_slice(&buf[..len]);
+                r.len += len;
+            }
+            required += len;
+        }
+
+        if required > r.len && !O::TRUNCATE {
+            return Err(PackingError::CapacityExceeded { capacity: N, required });
+        }
+        Ok(r)
+    }
+
+    /// The encoded bytes of the string, without the padding.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len]
+    }
+
+    /// The string, if its encoded bytes are also valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        core::str::from_utf8(self.as_bytes()).ok()
+    }
+
+    /// Decodes the characters of the string.
+    pub fn chars(&self) -> PackedStringChars<'_, E> {
+        PackedStringChars {
+            bytes: self.as_bytes(),
+            encoding: PhantomData
+        }
+    }
+
+    /// Length of the encoded string, in bytes.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<P, E, O, const N: usize> Default for PackedString<P, E, O, N> {
+    fn default() -> Self {
+        PackedString {
+            bytes: [0; N],
+            len: 0,
+            format: PhantomData
+        }
+    }
+}
+
+impl<'a, P, E, O, const N: usize> TryFrom<&'a str> for PackedString<P, E, O, N> where P: StringPadding, E: StringEncoding, O: StringOverflow {
+    type Error = PackingError;
+
+    fn try_from(s: &'a str) -> PackingResult<Self> {
+        Self::new(s)
+    }
+}
+
+impl<P, E, O, const N: usize> PartialEq<str> for PackedString<P, E, O, N> where P: StringPadding, E: StringEncoding, O: StringOverflow {
+    fn eq(&self, other: &str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl<'a, P, E, O, const N: usize> PartialEq<&'a str> for PackedString<P, E, O, N> where P: StringPadding, E: StringEncoding, O: StringOverflow {
+    fn eq(&self, other: &&'a str) -> bool {
+        self.chars().eq(other.chars())
+    }
+}
+
+impl<P, E, O, const N: usize> PartialEq<PackedString<P, E, O, N>> for &str where P: StringPadding, E: StringEncoding, O: StringOverflow {
+    fn eq(&self, other: &PackedString<P, E, O, N>) -> bool {
+        other == self
+    }
+}
+
+impl<P, E, O, const N: usize> Debug for PackedString<P, E, O, N> where P: StringPadding, E: StringEncoding, O: StringOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_char('"')?;
+        for c in self.chars() {
+            for e in c.escape_debug() {
+                f.write_char(e)?;
+            }
+        }
+        f.write_char('"')
+    }
+}
+
+impl<P, E, O, const N: usize> Display for PackedString<P, E, O, N> where P: StringPadding, E: StringEncoding, O: StringOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+        Ok(())
+    }
+}
+
+impl<P, E, O, const N: usize> PackedStruct for PackedString<P, E, O, N> where P: StringPadding, E: StringEncoding, O: StringOverflow {
+    type ByteArray = [u8; N];
+
+    fn pack(&self) -> PackingResult<[u8; N]> {
+        let mut packed = [P::PADDING; N];
+        packed[..self.len].copy_from_slice(self.as_bytes());
+        Ok(packed)
+    }
+
+    /// Fails with `PackingError::InvalidValue` if the bytes aren't valid in the encoding.
+    fn unpack(src: &[u8; N]) -> PackingResult<Self> {
+        let len = if P::PADDING == 0 {
+            src.iter().position(|b| *b == 0).unwrap_or(N)
+        } else {
+            src.iter().rposition(|b| *b != P::PADDING).map(|p| p + 1).unwrap_or(0)
+        };
+
+        if !E::is_valid(&src[..len]) {
+            return Err(PackingError::InvalidValue);
+        }
+
+        let mut r = Self::default();
+        r.bytes[..len].copy_from_slice(&src[..len]);
+        r.len = len;
+        Ok(r)
+    }
+}
+
+/// The decoded characters of a `PackedString`.
+pub struct PackedStringChars<'a, E> {
+    bytes: &'a [u8],
+    encoding: PhantomData<E>
+}
+
+impl<'a, E> Iterator for PackedStringChars<'a, E> where E: StringEncoding {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let (c, len) = E::decode_char(self.bytes)?;
+        self.bytes = &self.bytes[len..];
+        Some(c)
+    }
+}
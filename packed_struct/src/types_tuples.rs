@@ -1,6 +1,10 @@
 //! Tuples of types that can be packed together. Only byte-sized structures can be chained together.
+//! Tuples of up to 24 members are supported. Longer messages can nest tuples, as a tuple
+//! is a member like any other.
 //!
-//! Supports having one dynamically sized packed structure type within the tuple.
+//! Supports having one dynamically sized packed structure type within the tuple, followed only
+//! by statically sized ones. More dynamically sized members can be chained by wrapping
+//! them into a [`LengthPrefixed`](crate::types::LengthPrefixed), which packs their length in front of them.
 //!
 //! # Example with ad-hoc chained structures
 //!
@@ -38,101 +42,35 @@
 
 use crate::internal_prelude::v1::*;
 
-use crate::{PackedStructSlice, PackingError, PackingResult, lib_get_mut_slice, lib_get_slice};
+use crate::{PackedStructSlice, PackingError, PackingResult, lib_get_slice};
 
-#[derive(Debug, Copy, Clone, PartialEq)]
-enum StructLength {
-    Empty,
-    Dynamic,
-    Static(usize)
-}
-
-struct StructLengthBuilder {
-    lengths: [StructLength; 16],
-    i: usize
-}
-
-impl StructLengthBuilder {
-    fn new() -> Self {
-        StructLengthBuilder {
-            lengths: [StructLength::Empty; 16],
-            i: 0
-        }
-    }
-
-    fn add<S: PackedStructSlice>(&mut self, st: Option<&S>) -> PackingResult<StructLength> {
-        let len = match S::packed_bytes_size(st) {
-            Err(PackingError::InstanceRequiredForSize) => StructLength::Dynamic,
-            Err(e) => return Err(e),
-            Ok(s) => StructLength::Static(s)
-        };
-        let target = lib_get_mut_slice(&mut self.lengths, self.i)?;
-        *target = len;
-        self.i += 1;
-        Ok(len)
-    }
-
-    fn build(self, total_length: usize) -> PackingResult<StructLengths> {
-        // at most one can be dynamic!
-        let lengths = lib_get_slice(&self.lengths, ..self.i)?;
-        let dy = lengths.iter().filter(|l| l == &&StructLength::Dynamic).count();
-        if dy > 1 {
-            return Err(PackingError::MoreThanOneDynamicType);
-        }
-
-        let len_static: usize = lengths.iter().filter_map(|l| if let StructLength::Static(s) = l { Some(*s) } else { None }).sum();
-        let len_dy = if dy == 1 {
-            let l = total_length as isize - len_static as isize;
-            if l < 0 {
-                return Err(PackingError::BufferSizeMismatch { expected: len_static, actual: total_length });
+/// The bytes of the next member, which starts at `start`. A member that doesn't encode
+/// its own length takes up everything up to the statically sized members that follow it.
+fn member_range(src_len: usize, start: usize, member_size: PackingResult<usize>, following: &[PackingResult<usize>]) -> PackingResult<Range<usize>> {
+    match member_size {
+        Ok(size) => {
+            let end = start + size;
+            if end > src_len {
+                return Err(PackingError::BufferSizeMismatch { expected: end, actual: src_len });
+            }
+            Ok(start..end)
+        },
+        Err(PackingError::InstanceRequiredForSize) => {
+            let mut reserved = 0;
+            for size in following {
+                match size {
+                    Ok(size) => reserved += size,
+                    Err(PackingError::InstanceRequiredForSize) => return Err(PackingError::MoreThanOneDynamicType),
+                    Err(e) => return Err(*e)
+                }
             }
-            l as usize
-        } else {
-            0
-        };
-
-        if len_static + len_dy != total_length {
-            return Err(PackingError::BufferSizeMismatch { expected: len_static + len_dy, actual: total_length });
-        }
-
-        let mut output_lengths = [0; 16];
-        for (i, l) in lengths.iter().enumerate() {
-            let output = lib_get_mut_slice(&mut output_lengths, i)?;
-            *output = match l {
-                StructLength::Empty => return Err(PackingError::InternalError), /* shouldn't happen */
-                StructLength::Dynamic => len_dy,
-                StructLength::Static(s) => *s
-            };
-        }
-
-        StructLengths::new(output_lengths, self.i)
-    }
-}
-
-struct StructLengths {
-    ranges: [Range<usize>; 16],
-    len: usize
-}
-
-impl StructLengths {
-    fn new(lengths: [usize; 16], len: usize) -> PackingResult<Self> {
-        let mut ranges = [0..0, 0..0, 0..0, 0..0, 0..0, 0..0, 0..0, 0..0, 0..0, 0..0, 0..0, 0..0 , 0..0, 0..0, 0..0, 0..0];
-        let mut n = 0;
-        for i in 0..len {
-            let l = lib_get_slice(&lengths, i)?;
-            let ranges_out = lib_get_mut_slice(&mut ranges, i)?;
-            *ranges_out = n..(n+l);
-            n += l;
-        }
-
-        Ok(StructLengths {
-            ranges,
-            len
-        })
-    }
 
-    fn get_ranges(&self) -> PackingResult<&[Range<usize>]> {
-        lib_get_slice(&self.ranges, ..self.len)
+            if start + reserved > src_len {
+                return Err(PackingError::BufferSizeMismatch { expected: start + reserved, actual: src_len });
+            }
+            Ok(start..(src_len - reserved))
+        },
+        Err(e) => Err(e)
     }
 }
 
@@ -164,37 +102,50 @@ macro_rules! tuple_impls {
             $( $ntyp: PackedStructSlice ),*
         {
             fn pack_to_slice(&self, output: &mut [u8]) -> PackingResult<()> {
-                let lengths = {
-                    let mut builder = StructLengthBuilder::new();
-                    builder.add::<$typ>(Some(&self.$idx))?;
-                    $( builder.add::<$ntyp>(Some(&self.$nidx))?; )*
-                    builder.build(output.len())?
-                };
-
-                let ranges = lengths.get_ranges()?;
-
-                self.$idx.pack_to_slice(lib_get_mut_slice(output, ranges.get($idx).ok_or(crate::PackingError::InternalError)?.clone())?)?;
-                $( self.$nidx.pack_to_slice(lib_get_mut_slice(output, ranges.get($nidx).ok_or(crate::PackingError::InternalError)?.clone())?)?; )*
-                
+                let expected = Self::packed_bytes_size(Some(self))?;
+                if output.len() != expected {
+                    return Err(PackingError::BufferSizeMismatch { expected, actual: output.len() });
+                }
+
+                let output = self.$idx.pack_to_prefix(output)?;
+                $( let output = self.$nidx.pack_to_prefix(output)?; )*
+                let _ = output;
+
                 Ok(())
             }
 
             fn unpack_from_slice(src: &[u8]) -> PackingResult<Self> {
-                let lengths = {
-                    let mut builder = StructLengthBuilder::new();                    
-                    builder.add::<$typ>(None)?;
-                    $( builder.add::<$ntyp>(None)?; )*
-                    builder.build(src.len())?
-                };
-
-                let ranges = lengths.get_ranges()?;
-
-                Ok(
-                    (
-                        $typ::unpack_from_slice(lib_get_slice(src, ranges.get($idx).ok_or(crate::PackingError::InternalError)?.clone())?)?,
-                        $( $ntyp::unpack_from_slice(lib_get_slice(src, ranges.get($nidx).ok_or(crate::PackingError::InternalError)?.clone())?)? ),*
-                    )
-                )
+                // the statically known sizes, to find the end of a member that doesn't encode its own length
+                let sizes = [
+                    $typ::packed_bytes_size(None),
+                    $( $ntyp::packed_bytes_size(None) ),*
+                ];
+                if sizes.iter().all(|s| s.is_ok()) {
+                    let expected = sizes.iter().filter_map(|s| s.ok()).sum();
+                    if src.len() != expected {
+                        return Err(PackingError::BufferSizeMismatch { expected, actual: src.len() });
+                    }
+                }
+
+                let mut start = 0;
+                let unpacked = (
+                    {
+                        let range = member_range(src.len(), start, $typ::packed_bytes_size_from_prefix(lib_get_slice(src, start..)?), lib_get_slice(&sizes, ($idx + 1)..)?)?;
+                        start = range.end;
+                        $typ::unpack_from_slice(lib_get_slice(src, range)?)?
+                    },
+                    $({
+                        let range = member_range(src.len(), start, $ntyp::packed_bytes_size_from_prefix(lib_get_slice(src, start..)?), lib_get_slice(&sizes, ($nidx + 1)..)?)?;
+                        start = range.end;
+                        $ntyp::unpack_from_slice(lib_get_slice(src, range)?)?
+                    }),*
+                );
+
+                if start != src.len() {
+                    return Err(PackingError::BufferSizeMismatch { expected: start, actual: src.len() });
+                }
+
+                Ok(unpacked)
             }
 
             fn packed_bytes_size(opt_self: Option<&Self>) -> PackingResult<usize> {
@@ -210,6 +161,20 @@ macro_rules! tuple_impls {
 }
 
 tuple_impls!(
+    (23 => X),
+    (22 => W),
+    (21 => V),
+    (20 => U),
+    (19 => T),
+    (18 => S),
+    (17 => R),
+    (16 => Q),
+    (15 => P),
+    (14 => O),
+    (13 => N),
+    (12 => M),
+    (11 => L),
+    (10 => K),
     (9 => J),
     (8 => I),
     (7 => H),
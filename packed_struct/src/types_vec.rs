@@ -41,6 +41,15 @@ impl<T> PackedStructSlice for Vec<T> where T: PackedStructSlice {
         Ok(vec)
     }
 
+    /// Unpacks as many whole items as the buffer holds, a trailing partial item is
+    /// returned as the remainder.
+    fn unpack_from_prefix(src: &[u8]) -> Result<(Self, &[u8]), PackingError> {
+        let item_size = T::packed_bytes_size(None)?;
+        if item_size == 0 { return Ok((vec![], src)); }
+        let (items, rest) = src.split_at(src.len() - (src.len() % item_size));
+        Ok((Self::unpack_from_slice(items)?, rest))
+    }
+
     fn packed_bytes_size(opt_self: Option<&Self>) -> Result<usize, PackingError> {
         match opt_self {
             None => Err(PackingError::InstanceRequiredForSize),
@@ -1,5 +1,5 @@
 #![recursion_limit = "192"]
-#![allow(clippy::redundant_clone)]
+#![allow(clippy::redundant_clone, clippy::manual_is_multiple_of, clippy::explicit_counter_loop)]
 
 extern crate proc_macro;
 extern crate proc_macro2;
@@ -8,6 +8,8 @@ use crate::pack_parse::*;
 pub struct FieldMidPositioning {
     pub bit_width: usize,
     pub bits_position: BitsPositionParsed,
+    /// Distance between the starts of an array's elements, if they have gaps
+    pub element_stride_bits: Option<usize>
 }
 
 pub enum FieldKind {
@@ -17,7 +19,9 @@ pub enum FieldKind {
     },
     Array {
         ident: syn::Ident,
-        size: usize,
+        /// The lengths of the dimensions, outer first
+        dims: Vec<usize>,
+        /// All of the elements, in row-major order
         elements: Vec<FieldRegular>
     }
 }
@@ -29,7 +33,35 @@ pub struct FieldRegular {
     /// The range as parsed by our parser. A single byte: 0..7
     pub bit_range: Range<usize>,
     /// The range that can be used by rust's slices. A single byte: 0..8
-    pub bit_range_rust: Range<usize>
+    pub bit_range_rust: Range<usize>,
+    /// For `Option<T>` fields, how `None` is packed. `ty` is then the inner type.
+    pub optional: Option<FieldOptional>,
+    pub checksum: Option<FieldChecksum>,
+    /// A nested structure whose fields are shown individually
+    pub flatten: bool,
+    /// Another view of bits that belong to other fields. Packed after them, replacing their bits.
+    pub overlay: bool
+}
+
+/// The field is computed from a range of the packed bits.
+pub struct FieldChecksum {
+    pub kind: ChecksumKind,
+    /// Exclusive range of MSB0 bits. Whole bytes for checksum functions.
+    pub over: Range<usize>
+}
+
+pub enum ChecksumKind {
+    /// `fn(&[u8]) -> T`
+    Function(syn::Path),
+    /// A single bit that makes the number of ones even or odd
+    Parity { odd: bool }
+}
+
+pub enum FieldOptional {
+    /// A flag bit, set when the value is present. MSB0 position within the structure.
+    PresentBit(usize),
+    /// This raw integer value of the field stands for `None`
+    NoneValue(syn::Expr)
 }
 
 #[derive(Clone)]
@@ -40,14 +72,78 @@ pub enum SerializationWrapper {
     Endiannes {
         endian: syn::Type
     },
-    PrimitiveEnum
+    PrimitiveEnum,
+    /// A module with the `pack` and `unpack` functions for the field's bytes
+    With {
+        module: syn::Path,
+        bytes: usize
+    },
+    /// A `bool` packed as one of two bit patterns. Other patterns fail to unpack.
+    Bool {
+        true_value: u64,
+        false_value: u64,
+        bytes: usize
+    }
 }
 
 
+/// A dynamically sized field that follows all of the statically sized ones.
+pub struct FieldDynamic {
+    pub ident: syn::Ident,
+    /// The vector's type, `Vec<T>`, or the `Option<T>` of a conditional field
+    pub ty: syn::Type,
+    /// The type of a single element, `T`
+    pub element_ty: syn::Type,
+    pub length: DynamicLength
+}
+
+pub enum DynamicLength {
+    /// The number of elements is stored in a statically sized field
+    Count {
+        field: syn::Ident,
+        ty: syn::Type
+    },
+    /// The number of elements is packed in front of the elements
+    Prefix {
+        ty: syn::Type
+    },
+    /// The elements take up the rest of the buffer
+    Remainder,
+    /// An `Option<T>` that is packed only when the condition holds
+    Condition(syn::Expr)
+}
+
+
+/// A field that isn't packed, it is initialized when unpacking.
+pub struct FieldSkipped {
+    pub ident: syn::Ident,
+    /// `Default::default()` if not specified
+    pub default: Option<syn::Expr>
+}
+
+/// A nested structure without an explicit size. Only the compiler knows its size, the
+/// length of its `ByteArray`, so it is packed after the fields with known positions.
+pub struct FieldInferred {
+    pub ident: syn::Ident,
+    /// The nested structure, or the element type of an array
+    pub ty: syn::Type,
+    /// The lengths of the array's dimensions, outer first. Empty for a single structure.
+    pub dims: Vec<usize>,
+    pub flatten: bool,
+    /// The statically sized fields up to the next inferred one. Their bits are relative
+    /// to the end of this structure.
+    pub following: Vec<FieldKind>,
+    pub following_bytes: usize
+}
+
 pub struct PackStruct<'a> {
     pub fields: Vec<FieldKind>,
+    pub inferred_fields: Vec<FieldInferred>,
+    pub dynamic_fields: Vec<FieldDynamic>,
+    pub skipped_fields: Vec<FieldSkipped>,
     pub num_bytes: usize,
     pub num_bits: usize,
+    #[allow(dead_code)]
     pub data_struct: &'a syn::DataStruct,
     pub derive_input: &'a syn::DeriveInput
 }
@@ -7,7 +7,8 @@ use crate::common::*;
 use syn::spanned::Spanned;
 use crate::utils::*;
 
-use crate::utils_syn::tokens_to_string;
+use crate::utils_syn::{replace_self_fields, strip_self, tokens_to_string};
+use quote::ToTokens;
 
 pub fn derive_pack(parsed: &PackStruct) -> syn::Result<proc_macro2::TokenStream> {
 
@@ -15,65 +16,233 @@ pub fn derive_pack(parsed: &PackStruct) -> syn::Result<proc_macro2::TokenStream>
     let name = &parsed.derive_input.ident;
 
     let type_documentation = type_docs(parsed);
-    let num_bytes = parsed.num_bytes;
-    let num_bits = parsed.num_bits;
+    let size_checks = field_size_checks(parsed);
+    let (inferred_offsets, num_bytes) = inferred_offsets(parsed);
+    let num_bits = if parsed.inferred_fields.is_empty() {
+        let num_bits = parsed.num_bits;
+        quote! { #num_bits }
+    } else {
+        quote! { #num_bytes * 8 }
+    };
     
 
     let mut pack_fields = vec![];
     let mut unpack_fields = vec![];
     let mut unpack_struct_set = vec![];
+    let mut unpack_arrays = vec![];
+    let mut pack_checksums = vec![];
+    let mut pack_overlays = vec![];
 
     {
-        let mut reg  = |src: &dyn quote::ToTokens, target: &dyn quote::ToTokens, field: &FieldRegular| -> syn::Result<()> {
+        let mut reg  = |src: proc_macro2::TokenStream, target: &dyn quote::ToTokens, field: &FieldRegular, offset: Option<&proc_macro2::TokenStream>| -> syn::Result<()> {
             let bits = pack_bits(field);
 
-            let pack = pack_field(src, field);
+            let pack = pack_field(src.clone(), field);
             let unpack = unpack_field(field)?;
 
-            let pack_bits = bits.pack;
-            let unpack_bits = bits.unpack;
-
-            pack_fields.push(quote! {
-                {
-                    let packed = { #pack };
-                    #pack_bits
+            // the fields after a nested structure with an inferred size are positioned relative to its end
+            let (pack_bits, unpack_bits) = match offset {
+                None => (bits.pack, bits.unpack),
+                Some(offset) => {
+                    let (pack_bits, unpack_bits) = (bits.pack, bits.unpack);
+                    (quote! { let target = &mut target[#offset..]; #pack_bits }, quote! { let src = &src[#offset..]; #unpack_bits })
                 }
-            });
+            };
 
-            unpack_fields.push(quote! {
-                let #target = {
-                    let bytes = { #unpack_bits };
-                    #unpack
+            if let Some(ref checksum) = field.checksum {
+                let ty = &field.ty;
+                let start = checksum.over.start;
+                let end = checksum.over.end;
+
+                let compute = |buffer: proc_macro2::TokenStream| match checksum.kind {
+                    ChecksumKind::Function(ref function) => {
+                        let (start, end) = (start / 8, end / 8);
+                        quote! {
+                            #[allow(clippy::useless_conversion)]
+                            let checksum_value: #ty = (#function)(&#buffer[#start..#end]).into();
+                        }
+                    },
+                    ChecksumKind::Parity { odd } => quote! {
+                        let ones = (#start..#end).filter(|i| (#buffer[i / 8] >> (7 - (i % 8))) & 1 == 1).count();
+                        let checksum_value: bool = (ones % 2 == 1) != #odd;
+                    }
                 };
-            });
+                let compute_pack = compute(quote! { target });
+                let compute_unpack = compute(quote! { src });
+
+                // packed last, once all of the covered bits are in place
+                let pack = pack_field(quote! { checksum_value }, field);
+                pack_checksums.push((field.bit_range_rust.clone(), checksum.over.clone(), quote! {
+                    {
+                        #compute_pack
+                        let packed = { #pack };
+                        #pack_bits
+                    }
+                }));
+
+                unpack_fields.push(quote! {
+                    let #target = {
+                        let bytes = { #unpack_bits };
+                        #compute_unpack
+                        let packed = { #pack };
+                        if packed[..] != bytes[..] {
+                            return Err(PackingError::ChecksumMismatch);
+                        }
+                        #unpack
+                    };
+                });
+
+                return Ok(());
+            }
+
+            if field.overlay {
+                // the bits are packed by the fields that own them, the overlay has to agree
+                pack_overlays.push(quote! {
+                    {
+                        let packed = { #pack };
+                        let bytes = {
+                            let src = &target[..];
+                            #unpack_bits
+                        };
+                        if packed[..] != bytes[..] {
+                            return Err(PackingError::OverlayMismatch);
+                        }
+                    }
+                });
+            }
+
+            match field.optional {
+                None if field.overlay => {
+                    unpack_fields.push(quote! {
+                        let #target = {
+                            let bytes = { #unpack_bits };
+                            #unpack
+                        };
+                    });
+                },
+                None => {
+                    pack_fields.push(quote! {
+                        {
+                            let packed = { #pack };
+                            #pack_bits
+                        }
+                    });
+
+                    unpack_fields.push(quote! {
+                        let #target = {
+                            let bytes = { #unpack_bits };
+                            #unpack
+                        };
+                    });
+                },
+                Some(FieldOptional::PresentBit(bit)) => {
+                    let pack = pack_field(quote! { *value }, field);
+                    let flag_byte = bit / 8;
+                    let flag_mask = 1u8 << (7 - (bit % 8));
+
+                    // the payload stays zeroed for None
+                    pack_fields.push(quote! {
+                        if let Some(ref value) = #src {
+                            let packed = { #pack };
+                            #pack_bits
+                            target[#flag_byte] |= #flag_mask;
+                        }
+                    });
+
+                    unpack_fields.push(quote! {
+                        let #target = if (src[#flag_byte] & #flag_mask) != 0 {
+                            let bytes = { #unpack_bits };
+                            Some({ #unpack })
+                        } else {
+                            None
+                        };
+                    });
+                },
+                Some(FieldOptional::NoneValue(ref none_value)) => {
+                    let pack = pack_field(quote! { *value }, field);
+                    // the raw value skips the enum conversion
+                    let wrappers: Vec<_> = field.serialization_wrappers.iter().filter(|w| !matches!(w, SerializationWrapper::PrimitiveEnum)).cloned().collect();
+                    let pack_none = pack_field_with(quote! { #none_value }, &wrappers);
+
+                    pack_fields.push(quote! {
+                        {
+                            let none_packed = { #pack_none };
+                            let packed = match #src {
+                                Some(ref value) => {
+                                    let packed = { #pack };
+                                    if packed == none_packed {
+                                        return Err(PackingError::InvalidValue);
+                                    }
+                                    packed
+                                },
+                                None => none_packed
+                            };
+                            #pack_bits
+                        }
+                    });
+
+                    unpack_fields.push(quote! {
+                        let #target = {
+                            let bytes = { #unpack_bits };
+                            let none_packed = { #pack_none };
+                            if bytes[..] == none_packed[..] {
+                                None
+                            } else {
+                                Some({ #unpack })
+                            }
+                        };
+                    });
+                }
+            }
 
             Ok(())
         };
 
 
-        for field in &parsed.fields {
+        let following = parsed.inferred_fields.iter().zip(inferred_offsets.iter()).flat_map(|(inferred, offset)| {
+            let size = inferred_size(inferred);
+            let offset = quote! { (#offset + #size) };
+            inferred.following.iter().map(move |f| (f, Some(offset.clone())))
+        });
+
+        for (field, offset) in parsed.fields.iter().map(|f| (f, None)).chain(following) {
+            let offset = offset.as_ref();
             match field {
                 FieldKind::Regular { ref ident, ref field } => {
-                    reg(ident, ident, field)?;
+                    // count fields are filled in from their vector's length
+                    let count_of = parsed.dynamic_fields.iter().find(|d| matches!(d.length, DynamicLength::Count { field: ref c, .. } if c == ident));
+                    let src = match count_of {
+                        Some(dynamic) => {
+                            let ty = &field.ty;
+                            let vec_ident = &dynamic.ident;
+                            quote! { <#ty as ::packed_struct::types::ElementCount>::from_count(self.#vec_ident.len())? }
+                        },
+                        None => quote! { self.#ident }
+                    };
+                    reg(src, ident, field, offset)?;
 
                     unpack_struct_set.push(quote! {
                         #ident
                     });
                 },
-                FieldKind::Array { ref ident, ref elements, .. } => {
+                FieldKind::Array { ref ident, ref dims, ref elements } => {
                     let mut array_unpacked_elements = vec![];
                     for (i, field) in elements.iter().enumerate() {
-                        let src: syn::ExprIndex = syn::parse_str(&format!("{}[{}]", tokens_to_string(ident), i))?;
-                        let target: syn::Ident = syn::parse_str(&format!("{}_{}", tokens_to_string(ident), i))?;
+                        let indices = array_indices(dims, i);
+                        let src: syn::ExprIndex = syn::parse_str(&format!("{}{}", tokens_to_string(ident), array_indices_str(&indices)))?;
+                        let target = array_element_ident(ident, &indices);
 
-                        reg(&src, &target, field)?;
+                        reg(quote! { self.#src }, &target, field, offset)?;
                         array_unpacked_elements.push(target);
                     }
 
+                    let array = array_literal(dims, &array_unpacked_elements);
+                    unpack_arrays.push(quote! {
+                        let #ident = #array;
+                    });
+
                     unpack_struct_set.push(quote! {
-                        #ident: [
-                            #(#array_unpacked_elements),*
-                        ]
+                        #ident
                     });
                 }
             }        
@@ -81,6 +250,88 @@ pub fn derive_pack(parsed: &PackStruct) -> syn::Result<proc_macro2::TokenStream>
 
     }
 
+    for (inferred, offset) in parsed.inferred_fields.iter().zip(inferred_offsets.iter()) {
+        let ident = &inferred.ident;
+        let ty = &inferred.ty;
+        let size = nested_size(ty);
+
+        let mut reg_nested = |src: proc_macro2::TokenStream, target: &syn::Ident, offset: proc_macro2::TokenStream| {
+            pack_fields.push(quote! {
+                {
+                    let packed = { & #src }.pack()?;
+                    target[#offset..(#offset + #size)].copy_from_slice(&packed);
+                }
+            });
+
+            unpack_fields.push(quote! {
+                let #target = {
+                    let mut bytes = [0; #size];
+                    bytes.copy_from_slice(&src[#offset..(#offset + #size)]);
+                    <#ty>::unpack(&bytes)?
+                };
+            });
+        };
+
+        if inferred.dims.is_empty() {
+            reg_nested(quote! { self.#ident }, ident, offset.clone());
+        } else {
+            let mut array_unpacked_elements = vec![];
+            for i in 0..inferred.dims.iter().product() {
+                let indices = array_indices(&inferred.dims, i);
+                let target = array_element_ident(ident, &indices);
+                reg_nested(quote! { self.#ident #([#indices])* }, &target, quote! { (#offset + #i * #size) });
+                array_unpacked_elements.push(target);
+            }
+
+            let array = array_literal(&inferred.dims, &array_unpacked_elements);
+            unpack_arrays.push(quote! {
+                let #ident = #array;
+            });
+        }
+
+        unpack_struct_set.push(quote! {
+            #ident
+        });
+    }
+
+    // the arrays are assembled once all of their elements were unpacked
+    unpack_fields.extend(unpack_arrays);
+
+    for skipped in &parsed.skipped_fields {
+        let ident = &skipped.ident;
+        let default = match skipped.default {
+            Some(ref default) => quote! { #default },
+            None => quote! { ::core::default::Default::default() }
+        };
+
+        unpack_fields.push(quote! {
+            let #ident = #default;
+        });
+
+        unpack_struct_set.push(quote! {
+            #ident
+        });
+    }
+    // a checksum that covers other checksums has to be packed after them
+    while !pack_checksums.is_empty() {
+        let next = (0..pack_checksums.len()).find(|&i| {
+            let over = &pack_checksums[i].1;
+            pack_checksums.iter().enumerate().all(|(j, (bits, _, _))| i == j || bits.end <= over.start || over.end <= bits.start)
+        });
+
+        match next {
+            Some(i) => pack_fields.push(pack_checksums.remove(i).2),
+            None => return Err(syn::Error::new(parsed.derive_input.ident.span(), "The checksums of this structure cover each other."))
+        }
+    }
+
+    // overlays are compared to the packed bits, once all of them are in place
+    pack_fields.extend(pack_overlays);
+
+    if !parsed.dynamic_fields.is_empty() {
+        return derive_pack_dynamic(parsed, &pack_fields, &unpack_fields, &unpack_struct_set, &num_bytes);
+    }
+
     let result_ty = result_type();
 
     let debug_fmt = if include_debug_codegen() {
@@ -103,6 +354,8 @@ pub fn derive_pack(parsed: &PackStruct) -> syn::Result<proc_macro2::TokenStream>
     };
 
     let q = quote! {
+        #size_checks
+
         #type_documentation
         impl #impl_generics ::packed_struct::PackedStruct for #name #ty_generics #where_clause {
             type ByteArray = [u8; #num_bytes];
@@ -146,6 +399,285 @@ pub fn derive_pack(parsed: &PackStruct) -> syn::Result<proc_macro2::TokenStream>
 }
 
 
+/// Structures with dynamically sized fields can only be packed into slices. The
+/// statically sized fields come first, followed by the dynamic ones in their order.
+fn derive_pack_dynamic(parsed: &PackStruct, pack_fields: &[proc_macro2::TokenStream],
+                       unpack_fields: &[proc_macro2::TokenStream], unpack_struct_set: &[proc_macro2::TokenStream],
+                       num_bytes: &proc_macro2::TokenStream) -> syn::Result<proc_macro2::TokenStream>
+{
+    let (impl_generics, ty_generics, where_clause) = parsed.derive_input.generics.split_for_impl();
+    let name = &parsed.derive_input.ident;
+
+    let type_documentation = type_docs(parsed);
+    let size_checks = field_size_checks(parsed);
+    let result_ty = result_type();
+
+    let unpack_dynamic_set: Vec<_> = parsed.dynamic_fields.iter().map(|d| &d.ident).collect();
+    let mut pack_dynamic = vec![];
+    let mut unpack_dynamic = vec![];
+    let mut dynamic_sizes = vec![];
+
+    let count_values: Vec<_> = parsed.dynamic_fields.iter().filter_map(|d| match d.length {
+        DynamicLength::Count { ref field, ref ty } => {
+            let vec_ident = &d.ident;
+            Some((field, quote! { <#ty as ::packed_struct::types::ElementCount>::from_count(self.#vec_ident.len())? }))
+        },
+        _ => None
+    }).collect();
+
+    for dynamic in &parsed.dynamic_fields {
+        let ident = &dynamic.ident;
+        let ty = &dynamic.ty;
+        let element_ty = &dynamic.element_ty;
+
+        // the number of bytes taken up by the elements
+        let unpack_size = match dynamic.length {
+            DynamicLength::Count { field: ref count, ref ty } => {
+                pack_dynamic.push(quote! {
+                    let rest = self.#ident.pack_to_prefix(rest)?;
+                });
+
+                Some(quote! {
+                    <#ty as ::packed_struct::types::ElementCount>::to_count(&#count)?
+                        .checked_mul(<#element_ty as PackedStructSlice>::packed_bytes_size(None)?)
+                        .ok_or(PackingError::BufferTooSmall)?
+                })
+            },
+            DynamicLength::Prefix { ref ty } => {
+                // the length in bytes, like `LengthPrefixed`
+                pack_dynamic.push(quote! {
+                    let size = PackedStructSlice::packed_bytes_size(Some(&self.#ident))?;
+                    let rest = <#ty as ::packed_struct::types::PackedLength>::from_length(size)?.pack_to_prefix(rest)?;
+                    let rest = self.#ident.pack_to_prefix(rest)?;
+                });
+
+                dynamic_sizes.push(quote! {
+                    <#ty as PackedStructSlice>::packed_bytes_size(None)?
+                });
+
+                Some(quote! {
+                    {
+                        let (prefix, prefix_rest) = <#ty as PackedStructSlice>::unpack_from_prefix(rest)?;
+                        rest = prefix_rest;
+                        <#ty as ::packed_struct::types::PackedLength>::to_length(&prefix)?
+                    }
+                })
+            },
+            DynamicLength::Remainder => {
+                pack_dynamic.push(quote! {
+                    let rest = self.#ident.pack_to_prefix(rest)?;
+                });
+
+                None
+            },
+            DynamicLength::Condition(ref condition) => {
+                // evaluated against the fields that were already unpacked
+                let unpack_condition = strip_self(condition.to_token_stream());
+                // and against the written counts when packing
+                let pack_condition = replace_self_fields(condition.to_token_stream(), &count_values);
+
+                pack_dynamic.push(quote! {
+                    let rest = match ((#pack_condition), &self.#ident) {
+                        (true, Some(value)) => value.pack_to_prefix(rest)?,
+                        (false, None) => rest,
+                        _ => return Err(PackingError::InvalidValue)
+                    };
+                });
+
+                dynamic_sizes.push(quote! {
+                    match s.#ident {
+                        Some(ref value) => <#element_ty as PackedStructSlice>::packed_bytes_size(Some(value))?,
+                        None => 0
+                    }
+                });
+
+                unpack_dynamic.push(quote! {
+                    let #ident = if (#unpack_condition) {
+                        let (value, value_rest) = <#element_ty as PackedStructSlice>::unpack_from_prefix(rest)?;
+                        rest = value_rest;
+                        Some(value)
+                    } else {
+                        None
+                    };
+                });
+
+                continue;
+            }
+        };
+
+        dynamic_sizes.push(quote! {
+            <#ty as PackedStructSlice>::packed_bytes_size(Some(&s.#ident))?
+        });
+
+        match unpack_size {
+            Some(size) => {
+                unpack_dynamic.push(quote! {
+                    let #ident = {
+                        let size = #size;
+                        if rest.len() < size {
+                            return Err(PackingError::BufferTooSmall);
+                        }
+                        let (items, items_rest) = rest.split_at(size);
+                        rest = items_rest;
+                        <#ty as PackedStructSlice>::unpack_from_slice(items)?
+                    };
+                });
+            },
+            None => {
+                unpack_dynamic.push(quote! {
+                    let #ident = {
+                        let (items, items_rest) = <#ty as PackedStructSlice>::unpack_from_prefix(rest)?;
+                        rest = items_rest;
+                        items
+                    };
+                });
+            }
+        }
+    }
+
+    let q = quote! {
+        #size_checks
+
+        #type_documentation
+        impl #impl_generics ::packed_struct::PackedStructSlice for #name #ty_generics #where_clause {
+            #[allow(unused_imports, unused_parens)]
+            fn pack_to_slice(&self, output: &mut [u8]) -> ::packed_struct::PackingResult<()> {
+                use ::packed_struct::*;
+
+                let expected = Self::packed_bytes_size(Some(self))?;
+                if output.len() != expected {
+                    return Err(PackingError::BufferSizeMismatch { expected, actual: output.len() });
+                }
+
+                let mut target = [0 as u8; #num_bytes];
+
+                #(#pack_fields)*
+
+                let (fixed, rest) = output.split_at_mut(#num_bytes);
+                fixed.copy_from_slice(&target);
+
+                #(#pack_dynamic)*
+
+                let _ = rest;
+                Ok(())
+            }
+
+            #[allow(unused_imports, unused_parens, unused_assignments)]
+            fn unpack_from_slice(src: &[u8]) -> #result_ty <#name, ::packed_struct::PackingError> {
+                use ::packed_struct::*;
+
+                if src.len() < #num_bytes {
+                    return Err(PackingError::BufferTooSmall);
+                }
+                let src_len = src.len();
+                let (src, mut rest) = src.split_at(#num_bytes);
+
+                #(#unpack_fields)*
+
+                #(#unpack_dynamic)*
+
+                if !rest.is_empty() {
+                    return Err(PackingError::BufferSizeMismatch { expected: src_len - rest.len(), actual: src_len });
+                }
+
+                Ok(#name {
+                    #(#unpack_struct_set,)*
+                    #(#unpack_dynamic_set),*
+                })
+            }
+
+            #[allow(unused_imports)]
+            fn packed_bytes_size(opt_self: Option<&Self>) -> ::packed_struct::PackingResult<usize> {
+                use ::packed_struct::*;
+
+                let s = opt_self.ok_or(PackingError::InstanceRequiredForSize)?;
+                Ok(#num_bytes #(+ #dynamic_sizes)*)
+            }
+        }
+    };
+
+    Ok(q)
+}
+
+
+/// The length of a nested structure's `ByteArray`, evaluated by the compiler.
+pub fn nested_size(ty: &syn::Type) -> proc_macro2::TokenStream {
+    quote! { ::core::mem::size_of::<<#ty as ::packed_struct::PackedStruct>::ByteArray>() }
+}
+
+/// Bytes taken up by a nested structure with an inferred size, or by all of the elements
+/// of such an array.
+pub fn inferred_size(inferred: &FieldInferred) -> proc_macro2::TokenStream {
+    let size = nested_size(&inferred.ty);
+    let len: usize = inferred.dims.iter().product();
+    quote! { (#size * #len) }
+}
+
+/// The unpacked array element, like `values_1_3`
+fn array_element_ident(ident: &syn::Ident, indices: &[usize]) -> syn::Ident {
+    let indices: String = indices.iter().map(|i| format!("_{}", i)).collect();
+    syn::Ident::new(&format!("{}{}", ident, indices), ident.span())
+}
+
+/// The unpacked elements, nested into the array's dimensions.
+fn array_literal(dims: &[usize], elements: &[syn::Ident]) -> proc_macro2::TokenStream {
+    match dims {
+        [_, inner @ ..] if !inner.is_empty() => {
+            let rows = elements.chunks(inner.iter().product()).map(|row| array_literal(inner, row));
+            quote! { [#(#rows),*] }
+        },
+        _ => quote! { [#(#elements),*] }
+    }
+}
+
+/// Byte offsets of the nested structures with an inferred size, and the size of the
+/// whole structure. Constant expressions, as only the compiler knows the nested sizes.
+pub fn inferred_offsets(parsed: &PackStruct) -> (Vec<proc_macro2::TokenStream>, proc_macro2::TokenStream) {
+    let num_bytes = parsed.num_bytes;
+    let mut offset = quote! { #num_bytes };
+    let mut offsets = vec![];
+
+    for inferred in &parsed.inferred_fields {
+        let size = inferred_size(inferred);
+        let following_bytes = inferred.following_bytes;
+        offsets.push(quote! { (#offset) });
+        offset = quote! { #offset + #size + #following_bytes };
+    }
+
+    (offsets, quote! { (#offset) })
+}
+
+/// Compile time assertions that the explicitly sized nested structures take up as many
+/// bytes as their `ByteArray`.
+fn field_size_checks(parsed: &PackStruct) -> proc_macro2::TokenStream {
+    let mut checks = vec![];
+
+    let fields = parsed.fields.iter().chain(parsed.inferred_fields.iter().flat_map(|f| f.following.iter()));
+    for field in fields {
+        let (ident, field) = match field {
+            FieldKind::Regular { ref ident, ref field } => (ident, &**field),
+            FieldKind::Array { ref ident, ref elements, .. } => (ident, &elements[0])
+        };
+
+        if !field.serialization_wrappers.is_empty() {
+            continue;
+        }
+
+        let ty = &field.ty;
+        let size = nested_size(ty);
+        let bytes = (field.bit_width as f32 / 8.0).ceil() as usize;
+        let message = format!("The size of the field '{}' has to match the packed size of its type, {}.", ident, tokens_to_string(ty));
+
+        // a mismatch fails as an array length mismatch, which doesn't need `assert!` in constants
+        checks.push(quote_spanned! { ty.span() =>
+            #[doc = #message]
+            const _: [(); #bytes] = [(); #size];
+        });
+    }
+
+    quote! { #(#checks)* }
+}
+
 
 struct PackBitsCopy {
     pack: proc_macro2::TokenStream,
@@ -256,11 +788,33 @@ fn pack_bits(field: &FieldRegular) -> PackBitsCopy {
 }
 
 
-fn pack_field(name: &dyn quote::ToTokens, field: &FieldRegular) -> proc_macro2::TokenStream {
-    let mut output = quote! { (self.#name) };
+fn pack_field(src: proc_macro2::TokenStream, field: &FieldRegular) -> proc_macro2::TokenStream {
+    pack_field_with(src, &field.serialization_wrappers)
+}
+
+fn pack_field_with(src: proc_macro2::TokenStream, wrappers: &[SerializationWrapper]) -> proc_macro2::TokenStream {
+    let mut output = quote! { (#src) };
 
-    for wrapper in &field.serialization_wrappers {
+    for wrapper in wrappers {
         match wrapper {
+            SerializationWrapper::With { ref module, bytes } => {
+                return quote! {
+                    {
+                        let packed: [u8; #bytes] = #module::pack(& #output)?;
+                        packed
+                    }
+                };
+            },
+            SerializationWrapper::Bool { true_value, false_value, bytes } => {
+                return quote! {
+                    {
+                        let value: u64 = if #output { #true_value } else { #false_value };
+                        let mut packed = [0; #bytes];
+                        packed.copy_from_slice(&value.to_be_bytes()[(8 - #bytes)..]);
+                        packed
+                    }
+                };
+            },
             SerializationWrapper::PrimitiveEnum => {
                 output = quote! {
                     {
@@ -325,6 +879,23 @@ fn unpack_field(field: &FieldRegular) -> syn::Result<proc_macro2::TokenStream> {
 
                 i += 1;
             }
+            (Some(SerializationWrapper::With { ref module, bytes }), _) => {
+                unpack = quote! {
+                    let bytes: &[u8; #bytes] = & #unpack;
+                    #module::unpack(bytes)?
+                };
+            },
+            (Some(SerializationWrapper::Bool { true_value, false_value, bytes }), _) => {
+                unpack = quote! {
+                    let mut value = [0; 8];
+                    value[(8 - #bytes)..].copy_from_slice(& #unpack);
+                    match u64::from_be_bytes(value) {
+                        #true_value => true,
+                        #false_value => false,
+                        _ => return Err(PackingError::InvalidValue)
+                    }
+                };
+            },
             (Some(&SerializationWrapper::PrimitiveEnum), _) => {
                 let ty = &field.ty;
                 
@@ -4,6 +4,7 @@ use proc_macro2::Span;
 use quote::{ToTokens};
 use syn::parse_quote;
 use crate::utils::*;
+use crate::pack_codegen::{inferred_offsets, inferred_size, nested_size};
 
 pub fn struct_runtime_formatter(parsed: &PackStruct) -> syn::Result<proc_macro2::TokenStream> {
     let (impl_generics, ty_generics, where_clause) = parsed.derive_input.generics.split_for_impl();
@@ -12,53 +13,147 @@ pub fn struct_runtime_formatter(parsed: &PackStruct) -> syn::Result<proc_macro2:
     let stdlib_prefix = collections_prefix();
     let debug_fields_fn = syn::Ident::new(&format!("debug_fields_{}", snake_name), Span::call_site());
 
-    let display_header = format!("{} ({} {})",
-        name,
-        parsed.num_bytes,
-        if parsed.num_bytes == 1 { "byte" } else { "bytes" }
-    );
+    let display_header = if parsed.inferred_fields.is_empty() {
+        format!("{} ({} {})",
+            name,
+            parsed.num_bytes,
+            if parsed.num_bytes == 1 { "byte" } else { "bytes" }
+        )
+    } else {
+        format!("{} ({} bytes and its nested structures)", name, parsed.num_bytes)
+    };
+
+    let (inferred_offsets, num_bytes) = inferred_offsets(parsed);
     
+    // each field with its entry in the debug fields, and the nested fields for flattened ones
     let mut debug_fields = vec![];
-    for field in &parsed.fields {
-        match field {
-            FieldKind::Regular { ref ident, ref field } => {
-                let name_str = &ident.to_string();
-                let bits: syn::ExprRange = syn::parse_str(&format!("{}..{}", field.bit_range.start, field.bit_range.end))?;
-                
-                debug_fields.push(quote! {
+    {
+        let emit = |debug_fields: &mut Vec<_>, name_str: String, field: &FieldRegular, src: proc_macro2::TokenStream, offset: Option<&proc_macro2::TokenStream>| {
+            let (start, end) = (field.bit_range.start, field.bit_range.end);
+            let bits = match offset {
+                None => quote! { #start..#end },
+                Some(offset) => quote! { (#offset * 8 + #start)..(#offset * 8 + #end) }
+            };
+
+            let entry = quote! {
+                ::packed_struct::debug_fmt::DebugBitField {
+                    name: #name_str.into(),
+                    bits: #bits,
+                    display_value: format!("{:?}", #src).into()
+                }
+            };
+
+            let flattened = if field.flatten {
+                // the nested fields, moved to where the structure's packed bytes end up
+                let ty = &field.ty;
+                let start = match offset {
+                    None => quote! { #start },
+                    Some(offset) => quote! { (#offset * 8 + #start) }
+                };
+                let bit_width = field.bit_width;
+                let prefix = format!("{}.", name_str);
+                Some(quote! {
+                    {
+                        use ::packed_struct::types::bits::ByteArray;
+
+                        // the field holds the last bits of the nested bytes, like for any other
+                        // packed type, so the bits in front of those aren't part of the parent
+                        let nested_bits = <<#ty as ::packed_struct::PackedStruct>::ByteArray as ByteArray>::len() * 8;
+                        let dropped = nested_bits.saturating_sub(#bit_width);
+                        for mut f in ::packed_struct::debug_fmt::PackedStructDebug::debug_fields(&#src) {
+                            // nested fields that aren't packed into the parent aren't shown
+                            if let (Some(start), Some(end)) = (f.bits.start.checked_sub(dropped), f.bits.end.checked_sub(dropped)) {
+                                f.name = format!("{}{}", #prefix, f.name).into();
+                                f.bits = (start + #start)..(end + #start);
+                                fields.push(f);
+                            }
+                        }
+                    }
+                })
+            } else {
+                None
+            };
+
+            debug_fields.push((entry, flattened));
+            syn::Result::Ok(())
+        };
+
+        let emit_fields = |debug_fields: &mut Vec<_>, fields: &[FieldKind], offset: Option<&proc_macro2::TokenStream>| {
+            for field in fields {
+                match field {
+                    FieldKind::Regular { ref ident, ref field } => {
+                        emit(debug_fields, ident.to_string(), field, quote! { src.#ident }, offset)?;
+                    },
+                    FieldKind::Array { ref ident, ref dims, ref elements } => {
+                        for (i, field) in elements.iter().enumerate() {
+                            let indices = array_indices(dims, i);
+                            emit(debug_fields, format!("{}{}", ident, array_indices_str(&indices)), field, quote! { src.#ident #([#indices])* }, offset)?;
+                        }
+                    }
+                }
+            }
+
+            syn::Result::Ok(())
+        };
+
+        emit_fields(&mut debug_fields, &parsed.fields, None)?;
+
+        for (inferred, offset) in parsed.inferred_fields.iter().zip(inferred_offsets.iter()) {
+            let ident = &inferred.ident;
+            let ty = &inferred.ty;
+            let size = nested_size(ty);
+
+            let elements: Vec<_> = if inferred.dims.is_empty() {
+                vec![(ident.to_string(), quote! { src.#ident }, offset.clone())]
+            } else {
+                (0..inferred.dims.iter().product()).map(|i| {
+                    let indices = array_indices(&inferred.dims, i);
+                    (format!("{}{}", ident, array_indices_str(&indices)), quote! { src.#ident #([#indices])* }, quote! { (#offset + #i * #size) })
+                }).collect()
+            };
+
+            for (name_str, src, offset) in elements {
+                let entry = quote! {
                     ::packed_struct::debug_fmt::DebugBitField {
                         name: #name_str.into(),
-                        bits: #bits,
-                        display_value: format!("{:?}", src.#ident).into()
+                        bits: (#offset * 8)..((#offset + #size) * 8 - 1),
+                        display_value: format!("{:?}", #src).into()
                     }
-                });
-            },
-            FieldKind::Array { ref ident, ref elements, .. } => {
-                for (i, field) in elements.iter().enumerate() {
-                    let name_str = format!("{}[{}]", ident, i);
-                    let bits: syn::ExprRange = syn::parse_str(&format!("{}..{}", field.bit_range.start, field.bit_range.end))?;
-                    
-                    debug_fields.push(quote! {
-                        ::packed_struct::debug_fmt::DebugBitField {
-                            name: #name_str.into(),
-                            bits: #bits,
-                            display_value: format!("{:?}", src.#ident[#i]).into()
+                };
+
+                let flattened = if inferred.flatten {
+                    let prefix = format!("{}.", name_str);
+                    Some(quote! {
+                        for mut f in ::packed_struct::debug_fmt::PackedStructDebug::debug_fields(&#src) {
+                            f.name = format!("{}{}", #prefix, f.name).into();
+                            f.bits = (f.bits.start + #offset * 8)..(f.bits.end + #offset * 8);
+                            fields.push(f);
                         }
-                    });
-                }
-                
+                    })
+                } else {
+                    None
+                };
+
+                debug_fields.push((entry, flattened));
             }
+
+            let size = inferred_size(inferred);
+            emit_fields(&mut debug_fields, &inferred.following, Some(&quote! { (#offset + #size) }))?;
         }
     }
 
     let num_fields = debug_fields.len();
-    let num_bytes = parsed.num_bytes;
+    let debug_fields_entries = debug_fields.iter().map(|(entry, _)| entry);
+    let debug_fields_flattened = debug_fields.iter().map(|(entry, flattened)| match flattened {
+        Some(flattened) => flattened.clone(),
+        None => quote! { fields.push(#entry); }
+    });
     let result_ty = result_type();
 
     let q = quote! {
         #[doc(hidden)]
         pub fn #debug_fields_fn(src: &#name) -> [::packed_struct::debug_fmt::DebugBitField<'static>; #num_fields] {
-            [#(#debug_fields),*]
+            [#(#debug_fields_entries),*]
         }
 
         #[allow(unused_imports)]
@@ -66,7 +161,7 @@ pub fn struct_runtime_formatter(parsed: &PackStruct) -> syn::Result<proc_macro2:
             fn fmt_fields(&self, fmt: &mut #stdlib_prefix::fmt::Formatter) -> #result_ty <(), #stdlib_prefix::fmt::Error> {
                 use ::packed_struct::PackedStruct;
                 
-                let fields = #debug_fields_fn(self);
+                let fields = ::packed_struct::debug_fmt::PackedStructDebug::debug_fields(self);
                 let packed: [u8; #num_bytes] = self.pack()?;
                 ::packed_struct::debug_fmt::packable_fmt_fields(fmt, &packed, &fields)
             }
@@ -74,6 +169,13 @@ pub fn struct_runtime_formatter(parsed: &PackStruct) -> syn::Result<proc_macro2:
             fn packed_struct_display_header() -> &'static str {
                 #display_header
             }
+
+            fn debug_fields(&self) -> #stdlib_prefix::vec::Vec<::packed_struct::debug_fmt::DebugBitField<'static>> {
+                let src = self;
+                let mut fields = #stdlib_prefix::vec::Vec::new();
+                #(#debug_fields_flattened)*
+                fields
+            }
         }
 
         #[allow(unused_imports)]
@@ -105,16 +207,27 @@ pub fn type_docs(parsed: &PackStruct) -> proc_macro2::TokenStream {
         p.to_tokens(&mut doc);
     };
 
-    doc_html(&format!("Structure that can be packed an unpacked into {size_bytes} bytes.\r\n",
-        size_bytes = parsed.num_bytes
-    ));
+    if !parsed.inferred_fields.is_empty() {
+        doc_html(&format!("Structure that can be packed an unpacked into {size_bytes} bytes, followed by its nested structures. Their size is inferred by the compiler.\r\n",
+            size_bytes = parsed.num_bytes
+        ));
+    } else if parsed.dynamic_fields.is_empty() {
+        doc_html(&format!("Structure that can be packed an unpacked into {size_bytes} bytes.\r\n",
+            size_bytes = parsed.num_bytes
+        ));
+    } else {
+        doc_html(&format!("Structure that can be packed an unpacked into {size_bytes} bytes, followed by its dynamically sized fields.\r\n",
+            size_bytes = parsed.num_bytes
+        ));
+    }
 
     doc_html("<table>\r\n");
     doc_html("<thead><tr><td>Bit, MSB0</td><td>Name</td><td>Type</td></tr></thead>\r\n");
     doc_html("<tbody>\r\n");
 
     {
-        let mut emit_field_docs = |bits: &Range<usize>, field_ident, ty| {
+        let bool_ty: syn::Type = parse_quote!(bool);
+        let field_docs = |bits: &Range<usize>, field_ident: String, ty: &syn::Type, after: Option<&syn::Ident>| {
 
             let bits_str = {
                 if bits.start == bits.end {
@@ -123,26 +236,61 @@ pub fn type_docs(parsed: &PackStruct) -> proc_macro2::TokenStream {
                     format!("{}:{}", bits.start, bits.end)
                 }
             };
+            let bits_str = match after {
+                Some(nested) => format!("{} after {}", bits_str, nested),
+                None => bits_str
+            };
 
             // todo: friendly integer, reserved types. add LSB/MSB integer info.
 
-            doc_html(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>\r\n", bits_str, field_ident, tokens_to_string(ty)));
+            format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>\r\n", bits_str, field_ident, tokens_to_string(ty))
         };
 
-        for field in &parsed.fields {
-            match field {
-                FieldKind::Regular { ref ident, ref field } => {
-                    emit_field_docs(&field.bit_range, ident.to_string(), &field.ty);
-                },
-                FieldKind::Array { ref ident, ref elements, .. } => {
-                    for (i, field) in elements.iter().enumerate() {
-                        emit_field_docs(&field.bit_range, format!("{}[{}]", ident, i), &field.ty);
+        let fields_docs = |fields: &[FieldKind], after: Option<&syn::Ident>| {
+            let mut rows = vec![];
+            for field in fields {
+                match field {
+                    FieldKind::Regular { ref ident, ref field } => {
+                        rows.push(field_docs(&field.bit_range, ident.to_string(), &field.ty, after));
+                        if let Some(FieldOptional::PresentBit(bit)) = field.optional {
+                            rows.push(field_docs(&(bit..bit), format!("{} present", ident), &bool_ty, after));
+                        }
+                    },
+                    FieldKind::Array { ref ident, ref dims, ref elements } => {
+                        for (i, field) in elements.iter().enumerate() {
+                            rows.push(field_docs(&field.bit_range, format!("{}{}", ident, array_indices_str(&array_indices(dims, i))), &field.ty, after));
+                        }
                     }
                 }
-            }            
+            }
+            rows
+        };
+
+        for row in fields_docs(&parsed.fields, None) {
+            doc_html(&row);
+        }
+
+        for inferred in &parsed.inferred_fields {
+            let ty = inferred.dims.iter().rev().fold(tokens_to_string(&inferred.ty), |ty, len| format!("[{}; {}]", ty, len));
+            doc_html(&format!("<tr><td>nested</td><td>{}</td><td>{}</td></tr>\r\n", inferred.ident, ty));
+
+            for row in fields_docs(&inferred.following, Some(&inferred.ident)) {
+                doc_html(&row);
+            }
         }
     }
 
+    for field in &parsed.dynamic_fields {
+        let length = match field.length {
+            DynamicLength::Count { field: ref count, .. } => format!("{} elements", count),
+            DynamicLength::Prefix { ref ty } => format!("{} prefix", tokens_to_string(ty)),
+            DynamicLength::Remainder => "remainder".to_string(),
+            DynamicLength::Condition(ref condition) => format!("if {}", tokens_to_string(condition))
+        };
+
+        doc_html(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>\r\n", length, field.ident, tokens_to_string(&field.ty)));
+    }
+
 
     doc_html("</tbody>\r\n");
     doc_html("</table>\r\n");
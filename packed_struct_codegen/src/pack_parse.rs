@@ -6,9 +6,12 @@ use crate::pack_parse_attributes::*;
 
 use syn::Meta;
 use syn::Token;
+use syn::ext::IdentExt;
+use syn::parse::ParseStream;
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use crate::utils::*;
+use crate::common::alloc_supported;
 
 use std::ops::Range;
 
@@ -23,7 +26,7 @@ pub fn parse_sub_attributes(attributes: &[syn::Attribute], main_attribute: &str,
         }
 
         if attr.path().is_ident(main_attribute) {
-            let nested = attr.parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated);
+            let nested = attr.parse_args_with(|input: ParseStream| Punctuated::<Meta, Token![,]>::parse_terminated_with(input, parse_meta));
             let nested = if let Ok(nested) = nested {
                 nested
             } else {
@@ -31,7 +34,12 @@ pub fn parse_sub_attributes(attributes: &[syn::Attribute], main_attribute: &str,
             };
             for meta in nested {
                 match meta {            
-                    syn::Meta::Path(_) => (),
+                    syn::Meta::Path(path) => {
+                        // flags like skip
+                        if let Some(key) = path.get_ident() {
+                            r.push((key.to_string(), String::new()));
+                        }
+                    },
                     syn::Meta::List(_) => (),
                     syn::Meta::NameValue(nv) => {
                         if let (Some(key), syn::Expr::Lit(lit)) = (nv.path.get_ident(), &nv.value) {
@@ -48,6 +56,18 @@ pub fn parse_sub_attributes(attributes: &[syn::Attribute], main_attribute: &str,
     Ok(r)
 }
 
+/// Like the regular `Meta` parser, but also accepts keywords like `if` as names.
+fn parse_meta(input: ParseStream) -> syn::Result<Meta> {
+    if input.peek(syn::Ident::peek_any) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+        let ident = syn::Ident::parse_any(input)?;
+        let eq_token = input.parse()?;
+        let value = input.parse()?;
+        return Ok(Meta::NameValue(syn::MetaNameValue { path: ident.into(), eq_token, value }));
+    }
+
+    input.parse()
+}
+
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 /// https://en.wikipedia.org/wiki/Bit_numbering
@@ -95,6 +115,8 @@ fn get_builtin_type_bit_width(p: &syn::PathSegment) -> syn::Result<Option<usize>
         "u32" | "i32" => Ok(Some(32)),
         "u64" | "i64" => Ok(Some(64)),
         "ReservedZero" | "ReservedZeroes" | "ReservedOne" | "ReservedOnes" |
+        "ReservedZeroStrict" | "ReservedOneStrict" | "ReservedPreserve" |
+        "Const" | "ConstLsb" | "ConstBits" |
         "Integer" => {
             match p.arguments {
                 ::syn::PathArguments::AngleBracketed(ref args) => {
@@ -124,6 +146,34 @@ fn get_builtin_type_bit_width(p: &syn::PathSegment) -> syn::Result<Option<usize>
                 _ => Ok(None)
             }
         },
+        "Hamming74" | "Secded" => {
+            // every nibble of the integer is a codeword
+            let codeword_bits = if p.ident == "Hamming74" { 7 } else { 8 };
+            match p.arguments {
+                ::syn::PathArguments::AngleBracketed(ref args) => {
+                    match args.args.first() {
+                        Some(syn::GenericArgument::Type(syn::Type::Path(ty))) => {
+                            let bits = get_builtin_type_bit_width(get_single_segment(ty)?)?;
+                            Ok(bits.map(|bits| bits / 4 * codeword_bits))
+                        },
+                        _ => Ok(None)
+                    }
+                },
+                _ => Ok(None)
+            }
+        },
+        "FixedString" | "PackedString" => {
+            // the length in bytes is the last argument
+            match p.arguments {
+                ::syn::PathArguments::AngleBracketed(ref args) => {
+                    match args.args.last() {
+                        Some(syn::GenericArgument::Const(expr)) => Ok(Some(get_expr_int_val(expr)? * 8)),
+                        _ => Ok(None)
+                    }
+                },
+                _ => Ok(None)
+            }
+        },
         _ => {
             Ok(None)
         }
@@ -131,12 +181,49 @@ fn get_builtin_type_bit_width(p: &syn::PathSegment) -> syn::Result<Option<usize>
 }
 
 
+/// The `T` of an `Option<T>`
+fn get_option_inner(ty: &syn::Type) -> Option<&syn::Type> {
+    let segment = match ty {
+        syn::Type::Path(type_path) if type_path.path.segments.len() == 1 => &type_path.path.segments[0],
+        _ => return None
+    };
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) if segment.ident == "Option" && args.args.len() == 1 => {
+            match &args.args[0] {
+                syn::GenericArgument::Type(ty) => Some(ty),
+                _ => None
+            }
+        },
+        _ => None
+    }
+}
+
+/// The element type of a possibly multi-dimensional array, and the lengths of its
+/// dimensions, outer first.
+fn get_array_dims(type_array: &syn::TypeArray) -> syn::Result<(&syn::Type, Vec<usize>)> {
+    let mut dims = vec![];
+    let mut type_array = type_array;
+    loop {
+        let size = get_expr_int_val(&type_array.len)?;
+        if size == 0 { 
+            return Err(syn::Error::new(type_array.len.span(), "Arrays sized 0 are not supported."));
+        }
+        dims.push(size);
+
+        match *type_array.elem {
+            syn::Type::Array(ref inner) => type_array = inner,
+            ref elem => return Ok((elem, dims))
+        }
+    }
+}
+
 fn get_field_mid_positioning(field: &syn::Field) -> syn::Result<FieldMidPositioning> {
     
     let mut array_size = 1;
     let bit_width_builtin: Option<usize>;
 
-    let _ty = match &field.ty {
+    let _ty = match get_option_inner(&field.ty).unwrap_or(&field.ty) {
         syn::Type::Path(type_path) => {
             let segment = get_single_segment(type_path)?;
 
@@ -144,22 +231,17 @@ fn get_field_mid_positioning(field: &syn::Field) -> syn::Result<FieldMidPosition
             segment.clone()
         },
         syn::Type::Array(type_array) => {
-            
-            let path = match *type_array.elem {
+            let (elem, dims) = get_array_dims(type_array)?;
+
+            let path = match elem {
                 syn::Type::Path(ref p) => p,
-                _ => return Err(syn::Error::new(type_array.elem.span(), "Unknown array path type"))
+                _ => return Err(syn::Error::new(elem.span(), "Unknown array path type"))
             };
 
             let segment = get_single_segment(path)?;
             
             bit_width_builtin = get_builtin_type_bit_width(segment)?;
-            let size = get_expr_int_val(&type_array.len)?;
-
-            if size == 0 { 
-                return Err(syn::Error::new(type_array.len.span(), "Arrays sized 0 are not supported."));
-            }            
-            
-            array_size = size;
+            array_size = dims.iter().product();
 
             segment.clone()
         },
@@ -173,6 +255,40 @@ fn get_field_mid_positioning(field: &syn::Field) -> syn::Result<FieldMidPosition
         _ => None
     }).next().unwrap_or(BitsPositionParsed::Next);
 
+    let element_stride_bits = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::ElementStrideBits(bits) = *a { Some(bits) } else { None }).next();
+    if let Some(stride) = element_stride_bits {
+        if !matches!(field.ty, syn::Type::Array(_)) {
+            return Err(syn::Error::new(field.span(), "The 'element_stride_bits' attribute is only supported on arrays."));
+        }
+
+        // the gaps after all but the last element can be taken up by other fields
+        let element_bits = match field_attributes.iter().filter_map(|a| if let PackFieldAttribute::ElementSizeBits(bits) = *a { Some(bits) } else { None }).next() {
+            Some(bits) => bits,
+            None => match (bit_width_builtin, bits_position) {
+                (Some(bits), _) => bits,
+                (None, BitsPositionParsed::Range(a, b)) if b + 1 > a + (array_size - 1) * stride => b + 1 - a - (array_size - 1) * stride,
+                _ => return Err(syn::Error::new(field.span(), "Please specify the 'element_size_bits' of this array."))
+            }
+        };
+
+        if element_bits == 0 || element_bits > stride {
+            return Err(syn::Error::new(field.span(), format!("The elements of {} bits don't fit into their stride of {} bits.", element_bits, stride)));
+        }
+
+        let bit_width = (array_size - 1) * stride + element_bits;
+        if let BitsPositionParsed::Range(a, b) = bits_position {
+            if b + 1 - a != bit_width {
+                return Err(syn::Error::new(field.span(), format!("The strided elements take up {} bits, the field's range has {}.", bit_width, b + 1 - a)));
+            }
+        }
+
+        return Ok(FieldMidPositioning {
+            bit_width,
+            bits_position,
+            element_stride_bits
+        });
+    }
+
     let bit_width = if let Some(bits) = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::SizeBits(bits) = *a { Some(bits) } else { None }).next() {
         if array_size > 1 {
             return Err(syn::Error::new(field.span(), "Please use the 'element_size_bits' or 'element_size_bytes' for arrays."));
@@ -191,41 +307,135 @@ fn get_field_mid_positioning(field: &syn::Field) -> syn::Result<FieldMidPosition
 
     Ok(FieldMidPositioning {
         bit_width,
-        bits_position
+        bits_position,
+        element_stride_bits: None
     })
 }
 
 
-fn parse_field(field: &syn::Field, mp: &FieldMidPositioning, bit_range: &Range<usize>, default_endianness: Option<IntegerEndianness>) -> syn::Result<FieldKind> {
+/// Nested structures and arrays of them without a position or a size are sized by the
+/// compiler, from the length of their `ByteArray`.
+fn parse_inferred_field(field: &syn::Field) -> syn::Result<Option<FieldInferred>> {
+    let (ty, dims) = match &field.ty {
+        syn::Type::Array(type_array) => {
+            let (elem, dims) = get_array_dims(type_array)?;
+            (elem, dims)
+        },
+        ty => (ty, vec![])
+    };
+
+    let segment = match ty {
+        syn::Type::Path(type_path) if get_option_inner(ty).is_none() => get_single_segment(type_path)?,
+        _ => return Ok(None)
+    };
+
+    if get_builtin_type_bit_width(segment)?.is_some() {
+        return Ok(None);
+    }
+
+    let field_attributes = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?);
+    if field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::BitPosition(_) | PackFieldAttribute::BytePosition(_) | PackFieldAttribute::SizeBits(_) |
+                                                   PackFieldAttribute::ElementSizeBits(_) | PackFieldAttribute::Ty(_) | PackFieldAttribute::With(_)))
+    {
+        return Ok(None);
+    }
+
+    if field_attributes.iter().any(|a| !matches!(a, PackFieldAttribute::Flatten)) {
+        return Err(syn::Error::new(field.span(), "Nested structures without an explicit size only support the 'flatten' attribute."));
+    }
+
+    Ok(Some(FieldInferred {
+        ident: field.ident.clone().ok_or_else(|| syn::Error::new(field.span(), "Missing ident!"))?,
+        ty: ty.clone(),
+        dims,
+        flatten: field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Flatten)),
+        following: vec![],
+        following_bytes: 0
+    }))
+}
+
+fn parse_field(field: &syn::Field, mp: &FieldMidPositioning, bit_range: &Range<usize>, present_bit: Option<usize>, default_endianness: Option<IntegerEndianness>) -> syn::Result<FieldKind> {
+
+    let field_attributes = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?);
+    let none_value = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::NoneValue(v) = a { Some(v) } else { None }).next();
+
+    match (get_option_inner(&field.ty), present_bit, none_value) {
+        (Some(_), None, None) => {
+            return Err(syn::Error::new(field.span(), "Please specify how None is packed, with either 'present_bit' or 'none_value'."));
+        },
+        (Some(_), Some(_), Some(_)) => {
+            return Err(syn::Error::new(field.span(), "Please use either 'present_bit' or 'none_value', not both."));
+        },
+        (None, Some(_), _) | (None, _, Some(_)) => {
+            return Err(syn::Error::new(field.span(), "The 'present_bit' and 'none_value' attributes are only supported on Option<T> fields."));
+        },
+        _ => ()
+    }
+
+    let element_order = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::ElementOrder(o) = a { Some(*o) } else { None }).next();
 
     match &field.ty {
         syn::Type::Path(_) => {
+            if element_order.is_some() {
+                return Err(syn::Error::new(field.span(), "The 'element_order' attribute is only supported on arrays."));
+            }
+
+            let mut reg_field = parse_reg_field(field, get_option_inner(&field.ty).unwrap_or(&field.ty), bit_range, default_endianness)?;
+
+            if let Some(bit) = present_bit {
+                reg_field.optional = Some(FieldOptional::PresentBit(bit));
+            }
+
+            if let Some(none_value) = none_value {
+                if !reg_field.serialization_wrappers.iter().any(|w| matches!(w, SerializationWrapper::Integer { .. })) {
+                    return Err(syn::Error::new(field.span(), "The 'none_value' attribute is only supported on integer and enum fields."));
+                }
+                reg_field.optional = Some(FieldOptional::NoneValue(syn::parse_str(none_value)?));
+            }
+
+            if reg_field.flatten && reg_field.optional.is_some() {
+                return Err(syn::Error::new(field.span(), "Optional fields can't be flattened."));
+            }
+
             return Ok(
                 FieldKind::Regular {
-                    field: Box::new(parse_reg_field(field, &field.ty, bit_range, default_endianness)?),
+                    field: Box::new(reg_field),
                     ident: field.ident.clone().ok_or_else(|| syn::Error::new(field.span(), "Missing ident!"))?
                 }
             );
         },
         syn::Type::Array(type_array) => {
 
-            let size = get_expr_int_val(&type_array.len)?;
+            // the elements of arrays of arrays are placed in row-major order
+            let (elem, dims) = get_array_dims(type_array)?;
+            let size: usize = dims.iter().product();
 
-            let element_size_bits = mp.bit_width / size;
-            if (mp.bit_width % element_size_bits) != 0 {
-                return Err(syn::Error::new(type_array.span(), "Element and array size mismatch!"));
-            }
+            let (element_size_bits, stride_bits) = match mp.element_stride_bits {
+                Some(stride) => (mp.bit_width - (size - 1) * stride, stride),
+                None => {
+                    let element_size_bits = mp.bit_width / size;
+                    if element_size_bits == 0 || (mp.bit_width % element_size_bits) != 0 {
+                        return Err(syn::Error::new(type_array.span(), "Element and array size mismatch!"));
+                    }
+                    (element_size_bits, element_size_bits)
+                }
+            };
 
             let mut elements = vec![];
             for i in 0..size {
-                let s = bit_range.start + (i * element_size_bits);
+                // reversed, the first element is placed at the end of the field
+                let position = match element_order {
+                    Some(ElementOrder::Reverse) => size - 1 - i,
+                    _ => i
+                };
+                let s = bit_range.start + (position * stride_bits);
                 let element_bit_range = s..(s + element_size_bits - 1);
-                elements.push(parse_reg_field(field, &type_array.elem, &element_bit_range, default_endianness)?);
+                elements.push(parse_reg_field(field, elem, &element_bit_range, default_endianness)?);
             }
             
             return Ok(FieldKind::Array {
                 ident: field.ident.clone().ok_or_else(|| syn::Error::new(field.span(), "Missing ident!"))?,
-                size,
+                dims,
                 elements
             });
         },
@@ -250,6 +460,76 @@ fn parse_reg_field(field: &syn::Field, ty: &syn::Type, bit_range: &Range<usize>,
         _ => None
     }).next().is_some();
 
+    let with = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::With(m) = a { Some(m) } else { None }).next();
+    if let Some(module) = with {
+        if is_enum_ty || field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::IntEndiannes(_) | PackFieldAttribute::Flatten)) {
+            return Err(syn::Error::new(field.span(), "The 'with' module replaces the built-in encoding, it can't be combined with 'ty', 'endian' or 'flatten'."));
+        }
+
+        wrappers.push(SerializationWrapper::With {
+            module: syn::parse_str(module)?,
+            bytes: (bit_width as f32 / 8.0).ceil() as usize
+        });
+
+        return Ok(FieldRegular {
+            ty: ty.clone(),
+            serialization_wrappers: wrappers,
+            bit_width,
+            bit_range: bit_range.clone(),
+            bit_range_rust: bit_range.start..(bit_range.end + 1),
+            optional: None,
+            checksum: None,
+            flatten: false,
+            overlay: field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Overlay))
+        });
+    }
+
+    let invert = field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Invert));
+    let true_value = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::TrueValue(v) = *a { Some(v) } else { None }).next();
+    let false_value = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::FalseValue(v) = *a { Some(v) } else { None }).next();
+    if invert || true_value.is_some() || false_value.is_some() {
+        if ty_str != "bool" {
+            return Err(syn::Error::new(field.span(), "The 'invert', 'true_value' and 'false_value' attributes are only supported on bool fields."));
+        }
+
+        let (true_value, false_value) = match (invert, true_value, false_value) {
+            (true, None, None) if bit_width == 1 => (0, 1),
+            (true, _, _) => {
+                return Err(syn::Error::new(field.span(), "Only single bits can be inverted, please use 'true_value' and 'false_value' for wider fields."));
+            },
+            (false, Some(t), Some(f)) => (t, f),
+            _ => {
+                return Err(syn::Error::new(field.span(), "Please specify both the 'true_value' and the 'false_value'."));
+            }
+        };
+
+        if true_value == false_value || bit_width > 64 || (bit_width < 64 && (true_value | false_value) >> bit_width != 0) {
+            return Err(syn::Error::new(field.span(), format!("The 'true_value' and 'false_value' have to be different values that fit into {} bits.", bit_width)));
+        }
+
+        if field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::IntEndiannes(_) | PackFieldAttribute::Flatten | PackFieldAttribute::With(_) | PackFieldAttribute::Ty(_))) {
+            return Err(syn::Error::new(field.span(), "Bool patterns can't be combined with 'ty', 'endian', 'with' or 'flatten'."));
+        }
+
+        wrappers.push(SerializationWrapper::Bool {
+            true_value,
+            false_value,
+            bytes: (bit_width as f32 / 8.0).ceil() as usize
+        });
+
+        return Ok(FieldRegular {
+            ty: ty.clone(),
+            serialization_wrappers: wrappers,
+            bit_width,
+            bit_range: bit_range.clone(),
+            bit_range_rust: bit_range.start..(bit_range.end + 1),
+            optional: None,
+            checksum: None,
+            flatten: false,
+            overlay: field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Overlay))
+        });
+    }
+
     let needs_int_wrap = {
         let int_types = ["u8", "i8", "u16", "i16", "u32", "i32", "u64", "i64"];
         is_enum_ty || int_types.iter().any(|t| t == &ty_str)
@@ -305,17 +585,264 @@ fn parse_reg_field(field: &syn::Field, ty: &syn::Type, bit_range: &Range<usize>,
         wrappers.push(SerializationWrapper::Endiannes { endian: endiannes_wrap_ty });
     }
 
+    let flatten = field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Flatten));
+    if flatten && (!wrappers.is_empty() || ty_str == "bool") {
+        return Err(syn::Error::new(field.span(), "Only nested packed structures can be flattened."));
+    }
+
     Ok(FieldRegular {
         ty: ty.clone(),
         serialization_wrappers: wrappers,
         bit_width,
         bit_range: bit_range.clone(),
-        bit_range_rust: bit_range.start..(bit_range.end + 1)
+        bit_range_rust: bit_range.start..(bit_range.end + 1),
+        optional: None,
+        checksum: None,
+        flatten,
+        overlay: field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Overlay))
     })
 }
 
 
 
+/// The supported dynamically sized collections, `Vec<T>`, `heapless::Vec<T, N>` and
+/// `heapless::String<N>`. Returns the element type and whether an allocator is needed.
+fn get_dynamic_collection(ty: &syn::Type) -> Option<(syn::Type, bool)> {
+    let segment = match ty {
+        syn::Type::Path(type_path) => type_path.path.segments.last()?,
+        _ => return None
+    };
+    let args: Vec<_> = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => args.args.iter().collect(),
+        _ => return None
+    };
+
+    match (segment.ident.to_string().as_str(), args.as_slice()) {
+        ("Vec", [syn::GenericArgument::Type(element_ty)]) => Some((element_ty.clone(), true)),
+        ("Vec", [syn::GenericArgument::Type(element_ty), _]) => Some((element_ty.clone(), false)),
+        ("String", [_]) => Some((syn::parse_quote!(u8), false)),
+        _ => None
+    }
+}
+
+fn is_dynamic_field(field: &syn::Field) -> syn::Result<bool> {
+    if get_dynamic_collection(&field.ty).is_some() {
+        return Ok(true);
+    }
+
+    let field_attributes = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?);
+    Ok(field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Count(_) | PackFieldAttribute::LengthPrefix(_) | PackFieldAttribute::Condition(_))))
+}
+
+fn parse_dynamic_field(field: &syn::Field, is_last: bool, fixed_fields: &[FieldKind], default_endianness: Option<IntegerEndianness>) -> syn::Result<FieldDynamic> {
+    let ident = field.ident.clone().ok_or_else(|| syn::Error::new(field.span(), "Missing ident!"))?;
+
+    let field_attributes = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?);
+
+    let condition = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::Condition(c) = a { Some(c) } else { None }).next();
+    if let Some(condition) = condition {
+        let element_ty = get_option_inner(&field.ty)
+            .ok_or_else(|| syn::Error::new(field.ty.span(), "Conditional fields have to be an Option<T>."))?;
+
+        if field_attributes.iter().any(|a| !matches!(a, PackFieldAttribute::Condition(_))) {
+            return Err(syn::Error::new(field.span(), "Conditional fields don't support any other attributes."));
+        }
+
+        return Ok(FieldDynamic {
+            ident,
+            ty: field.ty.clone(),
+            element_ty: element_ty.clone(),
+            length: DynamicLength::Condition(syn::parse_str(condition)?)
+        });
+    }
+
+    let (element_ty, needs_alloc) = get_dynamic_collection(&field.ty)
+        .ok_or_else(|| syn::Error::new(field.ty.span(), "Dynamically sized fields have to be a Vec<T>, heapless::Vec<T, N> or heapless::String<N>."))?;
+
+    if needs_alloc && !alloc_supported() {
+        return Err(syn::Error::new(field.span(), "Vec<T> fields require the std or alloc feature, consider heapless::Vec<T, N>."));
+    }
+
+    if field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::BitPosition(_) | PackFieldAttribute::BytePosition(_) | PackFieldAttribute::SizeBits(_) | PackFieldAttribute::ElementSizeBits(_))) {
+        return Err(syn::Error::new(field.span(), "Dynamically sized fields can't be positioned or sized, they are packed after the statically sized fields."));
+    }
+
+    let count = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::Count(c) = a { Some(c) } else { None }).next();
+    let prefix = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::LengthPrefix(p) = a { Some(p) } else { None }).next();
+    let endianness = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::IntEndiannes(e) = a { Some(*e) } else { None }).next().or(default_endianness);
+
+    let length = match (count, prefix) {
+        (Some(_), Some(_)) => {
+            return Err(syn::Error::new(field.span(), "Please use either 'count' or 'length_prefix', not both."));
+        },
+        (Some(count), None) => {
+            let count_field = fixed_fields.iter().filter_map(|f| match f {
+                FieldKind::Regular { ident, field } if ident == count => Some(field),
+                _ => None
+            }).next();
+
+            match count_field {
+                Some(count_field) => DynamicLength::Count {
+                    field: syn::parse_str(count)?,
+                    ty: count_field.ty.clone()
+                },
+                None => {
+                    return Err(syn::Error::new(field.span(), format!("The count field {} has to be a statically sized, non-array field of this structure.", count)));
+                }
+            }
+        },
+        (None, Some(prefix)) => {
+            let bits = match prefix.as_str() {
+                "u8" => Some(8),
+                "u16" => Some(16),
+                "u32" => Some(32),
+                "u64" => Some(64),
+                _ => None
+            };
+
+            let ty = match (bits, endianness) {
+                (Some(8), _) => syn::parse_str("u8")?,
+                (Some(bits), Some(endianness)) => {
+                    let ty_prefix = match endianness {
+                        IntegerEndianness::Msb => "Msb",
+                        IntegerEndianness::Lsb => "Lsb"
+                    };
+                    syn::parse_str(&format!("::packed_struct::types::{}Integer<{}, ::packed_struct::types::bits::Bits::<{}>, ::packed_struct::types::Integer<{}, ::packed_struct::types::bits::Bits::<{}>>>",
+                        ty_prefix, prefix, bits, prefix, bits))?
+                },
+                (Some(_), None) => {
+                    return Err(syn::Error::new(field.span(), "Please specify the endianness of the length prefix on the field or a default for the struct."));
+                },
+                (None, _) => syn::parse_str(prefix)?
+            };
+
+            DynamicLength::Prefix { ty }
+        },
+        (None, None) if is_last => DynamicLength::Remainder,
+        (None, None) => {
+            return Err(syn::Error::new(field.span(), "Please specify the 'count' or the 'length_prefix' of this field. Only the last field can take up the rest of the buffer."));
+        }
+    };
+
+    Ok(FieldDynamic {
+        ident,
+        ty: field.ty.clone(),
+        element_ty,
+        length
+    })
+}
+
+fn parse_skipped_field(field: &syn::Field) -> syn::Result<Option<FieldSkipped>> {
+    let field_attributes = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?);
+    let skip = field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Skip));
+    let default = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::Default(d) = a { Some(d) } else { None }).next();
+
+    if !skip {
+        if default.is_some() {
+            return Err(syn::Error::new(field.span(), "The 'default' attribute requires 'skip'."));
+        }
+        return Ok(None);
+    }
+
+    if field_attributes.iter().any(|a| !matches!(a, PackFieldAttribute::Skip | PackFieldAttribute::Default(_))) {
+        return Err(syn::Error::new(field.span(), "Skipped fields aren't packed, they only support the 'default' attribute."));
+    }
+
+    Ok(Some(FieldSkipped {
+        ident: field.ident.clone().ok_or_else(|| syn::Error::new(field.span(), "Missing ident!"))?,
+        default: default.map(|d| syn::parse_str(d)).transpose()?
+    }))
+}
+
+const BUILTIN_CHECKSUMS: &[&str] = &["crc8", "crc16_ccitt", "crc16_xmodem", "crc16_modbus", "crc32", "sum8", "sum16", "xor8"];
+
+fn parse_checksum(field: &syn::Field, parsed: &mut FieldKind, num_bytes: usize, bit_numbering: Option<BitNumbering>) -> syn::Result<()> {
+    let field_attributes = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?);
+    let checksum = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::Checksum(c) = a { Some(c) } else { None }).next();
+    let over = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::ChecksumOver(o) = a { Some(o) } else { None }).next();
+    let parity = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::Parity(odd) = a { Some(*odd) } else { None }).next();
+    let over_bits = field_attributes.iter().filter_map(|a| if let PackFieldAttribute::ParityOver(o) = a { Some(*o) } else { None }).next();
+
+    let reg_field = match parsed {
+        _ if checksum.is_none() && parity.is_none() => {
+            if over.is_some() || over_bits.is_some() {
+                return Err(syn::Error::new(field.span(), "The 'over' and 'over_bits' attributes require a 'checksum' or 'parity'."));
+            }
+            return Ok(());
+        },
+        FieldKind::Regular { field: reg_field, .. } if reg_field.optional.is_none() => reg_field,
+        _ => {
+            return Err(syn::Error::new(field.span(), "Checksums are only supported on regular, non-optional fields."));
+        }
+    };
+
+    let num_bits = num_bytes * 8;
+    let field_bits = reg_field.bit_range_rust.clone();
+
+    let (kind, over) = match (checksum, parity) {
+        (Some(_), Some(_)) => {
+            return Err(syn::Error::new(field.span(), "Please use either 'checksum' or 'parity', not both."));
+        },
+        (Some(checksum), None) => {
+            if over_bits.is_some() {
+                return Err(syn::Error::new(field.span(), "Checksums cover whole bytes, please use 'over'."));
+            }
+
+            let function = if BUILTIN_CHECKSUMS.contains(&checksum.as_str()) {
+                syn::parse_str(&format!("::packed_struct::checksum::{}", checksum))?
+            } else {
+                syn::parse_str(checksum)?
+            };
+
+            let over = match over {
+                Some(over) => {
+                    let (start, end) = parse_byte_range(over).map_err(|e| syn::Error::new(field.span(), e))?;
+                    (start * 8)..end.map(|e| e * 8).unwrap_or(num_bits)
+                },
+                None => 0..(field_bits.start / 8 * 8)
+            };
+
+            (ChecksumKind::Function(function), over)
+        },
+        (None, Some(odd)) => {
+            if tokens_to_string(&reg_field.ty) != "bool" {
+                return Err(syn::Error::new(field.ty.span(), "Parity fields have to be a bool."));
+            }
+
+            let over = match (over, over_bits, bit_numbering) {
+                (Some(_), _, _) => {
+                    return Err(syn::Error::new(field.span(), "Parity bits cover a range of bits, please use 'over_bits'."));
+                },
+                (None, None, _) => 0..field_bits.start,
+                (None, Some(BitsPositionParsed::Range(a, b)), Some(BitNumbering::Lsb0)) if b < num_bits => (num_bits - 1 - b)..(num_bits - a),
+                (None, Some(BitsPositionParsed::Range(a, b)), _) => a..(b + 1),
+                (None, Some(BitsPositionParsed::Start(a)), Some(BitNumbering::Msb0)) => a..num_bits,
+                (None, Some(_), _) => {
+                    return Err(syn::Error::new(field.span(), "Please specify an explicit range of bits with 'over_bits'."));
+                }
+            };
+
+            (ChecksumKind::Parity { odd }, over)
+        }
+        (None, None) => {
+            return Err(syn::Error::new(field.span(), "Checksum fields need a 'checksum' or a 'parity' attribute."));
+        }
+    };
+
+    if over.start >= over.end || over.end > num_bits {
+        return Err(syn::Error::new(field.span(), format!("The checksum has to cover some of the structure's {} bits, got {:?}.", num_bits, over)));
+    }
+
+    if over.start < field_bits.end && field_bits.start < over.end {
+        return Err(syn::Error::new(field.span(), format!("The checksum can't cover its own bits, {:?}.", field_bits)));
+    }
+
+    reg_field.checksum = Some(FieldChecksum { kind, over });
+
+    Ok(())
+}
+
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum BitsPositionParsed {
     Next,
@@ -340,10 +867,20 @@ impl BitsPositionParsed {
 
 
 pub fn parse_num(s: &str) -> Result<usize, String> {
+    let n = parse_u64(s)?;
+    if n > usize::MAX as u64 {
+        return Err(format!("The number {:?} is too large for this platform.", s.trim()));
+    }
+    Ok(n as usize)
+}
+
+pub fn parse_u64(s: &str) -> Result<u64, String> {
     let s = s.trim();
 
     if s.starts_with("0x") || s.starts_with("0X") {
-        usize::from_str_radix(&s[2..], 16).map_err(|e| { format!("Invalid hex number: {:?}, parse error: {:?}", s, e) })
+        u64::from_str_radix(&s[2..], 16).map_err(|e| { format!("Invalid hex number: {:?}, parse error: {:?}", s, e) })
+    } else if s.starts_with("0b") || s.starts_with("0B") {
+        u64::from_str_radix(&s[2..].replace('_', ""), 2).map_err(|e| { format!("Invalid binary number: {:?}, parse error: {:?}", s, e) })
     } else {
         s.parse().map_err(|e| format!("Invalid decimal number: {:?}, parse error: {:?}", s, e))
     }
@@ -351,14 +888,23 @@ pub fn parse_num(s: &str) -> Result<usize, String> {
 
 
 
-pub fn parse_struct(ast: &syn::DeriveInput) -> syn::Result<PackStruct> {
+pub fn parse_struct(ast: &syn::DeriveInput) -> syn::Result<PackStruct<'_>> {
     let attributes = PackStructAttribute::parse_all(&parse_sub_attributes(&ast.attrs, "packed_struct", "packed_field")?);
 
     let data_struct = match &ast.data {
         syn::Data::Struct(data) => data,
         _ => return Err(syn::Error::new(ast.span(), "#[derive(PackedStruct)] can only be used with braced structs"))
     };
-    let fields: Vec<_> = data_struct.fields.iter().collect();
+
+    // skipped fields don't take part in the layout
+    let mut all_fields = vec![];
+    let mut skipped_fields = vec![];
+    for field in &data_struct.fields {
+        match parse_skipped_field(field)? {
+            Some(skipped) => skipped_fields.push(skipped),
+            None => all_fields.push(field)
+        }
+    }
 
     if !ast.generics.params.is_empty() {
         return Err(syn::Error::new(ast.span(), "Structures with generic fields currently aren't supported."));
@@ -384,20 +930,51 @@ pub fn parse_struct(ast: &syn::DeriveInput) -> syn::Result<PackStruct> {
         }
     }).next();
 
+    // dynamically sized fields are packed after all of the statically sized ones
+    let mut num_fixed_fields = all_fields.len();
+    for (i, field) in all_fields.iter().enumerate() {
+        if is_dynamic_field(field)? {
+            num_fixed_fields = i;
+            break;
+        }
+    }
+    let (fields, dynamic_fields) = all_fields.split_at(num_fixed_fields);
+    for field in dynamic_fields {
+        if !is_dynamic_field(field)? {
+            return Err(syn::Error::new(field.span(), "Statically sized fields have to precede all of the dynamically sized fields."));
+        }
+    }
+
     let first_field_is_auto_positioned = {
         if let Some(field) = fields.first() {
-            let mp = get_field_mid_positioning(field)?;
-            mp.bits_position == BitsPositionParsed::Next
+            parse_inferred_field(field)?.is_some() || get_field_mid_positioning(field)?.bits_position == BitsPositionParsed::Next
         } else {
             false
         }
     };
 
     let mut fields_parsed: Vec<FieldKind> = vec![];
+    let mut positioned_fields = vec![];
+    let mut inferred_fields: Vec<FieldInferred> = vec![];
     {
         let mut prev_bit_range = None;
-        for field in &fields {
+        for field in fields {
+            if let Some(inferred) = parse_inferred_field(field)? {
+                if struct_size_bytes.is_some() {
+                    return Err(syn::Error::new(field.span(), "Please specify the size of this nested structure, sizes are only inferred in structures without an explicit size."));
+                }
+
+                // the fields that follow are positioned relative to the end of this structure
+                inferred_fields.push(inferred);
+                prev_bit_range = None;
+                continue;
+            }
+
             let mp = get_field_mid_positioning(field)?;
+            if !inferred_fields.is_empty() && mp.bits_position != BitsPositionParsed::Next {
+                return Err(syn::Error::new(field.span(), "The fields after a nested structure with an inferred size can't be positioned explicitly."));
+            }
+
             let bits_position = match (bit_positioning, mp.bits_position) {
                 (Some(BitNumbering::Lsb0), BitsPositionParsed::Next) | (Some(BitNumbering::Lsb0), BitsPositionParsed::Start(_)) => {
                     return Err(syn::Error::new(field.span(), "LSB0 field positioning currently requires explicit, full field positions."));
@@ -419,10 +996,70 @@ pub fn parse_struct(ast: &syn::DeriveInput) -> syn::Result<PackStruct> {
             };
             let bit_range = bits_position.to_bits_position().get_bits_range(mp.bit_width, &prev_bit_range);
 
-            fields_parsed.push(parse_field(field, &mp, &bit_range, default_int_endianness)?);
+            let present_bit = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?)
+                .iter()
+                .filter_map(|a| if let PackFieldAttribute::PresentBit(b) = a { Some(*b) } else { None })
+                .next();
+            let present_bit = match (bit_positioning, present_bit) {
+                (_, None) => None,
+                (Some(BitNumbering::Msb0), Some(bit)) => Some(bit),
+                (Some(BitNumbering::Lsb0), Some(bit)) => {
+                    match struct_size_bytes {
+                        Some(struct_size_bytes) if bit < struct_size_bytes * 8 => Some((struct_size_bytes * 8) - 1 - bit),
+                        _ => return Err(syn::Error::new(field.span(), "LSB0 field positioning currently requires explicit struct byte size."))
+                    }
+                },
+                (None, Some(_)) => {
+                    return Err(syn::Error::new(field.span(), "Please explicitly specify the bit numbering mode on the struct with an attribute: #[packed_struct(bit_numbering=\"msb0\")] or \"lsb0\"."));
+                }
+            };
+
+            let field_attributes = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?);
+            let overlay = field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Overlay));
+            if overlay {
+                if mp.bits_position == BitsPositionParsed::Next {
+                    return Err(syn::Error::new(field.span(), "Overlay fields share the bits of other fields, please position them explicitly."));
+                }
 
-            prev_bit_range = Some(bit_range);
+                if get_option_inner(&field.ty).is_some() || field_attributes.iter().any(|a| matches!(a, PackFieldAttribute::Checksum(_) | PackFieldAttribute::Parity(_))) {
+                    return Err(syn::Error::new(field.span(), "Overlay fields can't be optional or checksums."));
+                }
+            }
+
+            let parsed = parse_field(field, &mp, &bit_range, present_bit, default_int_endianness)?;
+            match inferred_fields.last_mut() {
+                Some(inferred) => {
+                    let checked = PackFieldAttribute::parse_all(&parse_sub_attributes(&field.attrs, "packed_field", "packed_struct")?)
+                        .iter()
+                        .any(|a| matches!(a, PackFieldAttribute::Checksum(_) | PackFieldAttribute::Parity(_)));
+                    if checked || present_bit.is_some() {
+                        return Err(syn::Error::new(field.span(), "Checksums and present bits have to precede the nested structures with an inferred size."));
+                    }
+                    inferred.following.push(parsed);
+                },
+                None => {
+                    fields_parsed.push(parsed);
+                    positioned_fields.push(*field);
+                }
+            }
+
+            // the following fields are placed after the field that this one overlays
+            if !overlay {
+                prev_bit_range = Some(bit_range);
+            }
+        }
+    }
+
+    for inferred in &mut inferred_fields {
+        let following_bits = inferred.following.iter().map(|f| match f {
+            FieldKind::Regular { ref field, .. } => field.bit_range_rust.end,
+            FieldKind::Array { ref elements, .. } => elements.iter().map(|e| e.bit_range_rust.end).max().unwrap()
+        }).max().unwrap_or(0);
+
+        if following_bits % 8 != 0 {
+            return Err(syn::Error::new(inferred.ident.span(), "The fields after a nested structure with an inferred size have to end at a byte boundary."));
         }
+        inferred.following_bytes = following_bits / 8;
     }
 
     let num_bits: usize = {
@@ -430,9 +1067,12 @@ pub fn parse_struct(ast: &syn::DeriveInput) -> syn::Result<PackStruct> {
             struct_size_bytes * 8
         } else {
             let last_bit = fields_parsed.iter().map(|f| match f {
-                FieldKind::Regular { ref field, .. } => field.bit_range_rust.end,
-                FieldKind::Array { ref elements, .. } => elements.last().unwrap().bit_range_rust.end
-            }).max().unwrap();
+                FieldKind::Regular { ref field, .. } => match field.optional {
+                    Some(FieldOptional::PresentBit(bit)) => ::std::cmp::max(bit + 1, field.bit_range_rust.end),
+                    _ => field.bit_range_rust.end
+                },
+                FieldKind::Array { ref elements, .. } => elements.iter().map(|e| e.bit_range_rust.end).max().unwrap()
+            }).max().unwrap_or(0);
             last_bit
         }
     };
@@ -443,40 +1083,108 @@ pub fn parse_struct(ast: &syn::DeriveInput) -> syn::Result<PackStruct> {
         return Err(syn::Error::new(fields[0].span(), "Please explicitly position the bits of the first field of this structure, as the alignment isn't obvious to the end user."));
     }
 
+    if !inferred_fields.is_empty() && (num_bits % 8) != 0 {
+        return Err(syn::Error::new(inferred_fields[0].ident.span(), "The fields in front of a nested structure with an inferred size have to end at a byte boundary."));
+    }
+
     // check for overlaps
     {
         let mut bits = vec![None; num_bytes * 8];
-        for field in &fields_parsed {
-            let mut find_overlaps = |name: String, range: &Range<usize>| {
-                for i in range.start .. (range.end+1) {
-                    if let Some(Some(n)) = bits.get(i) {
-                        return Err(syn::Error::new(name.span(), format!("Overlap in bits between fields {} and {}", n, name)));
+        let mut overlay_bits = vec![None; num_bytes * 8];
+
+        let field_ranges = |field: &FieldKind| -> Vec<(String, Range<usize>, bool)> {
+            match field {
+                FieldKind::Regular { ref field, ref ident } => {
+                    let mut ranges = vec![(ident.to_string(), field.bit_range.clone(), field.overlay)];
+                    if let Some(FieldOptional::PresentBit(bit)) = field.optional {
+                        ranges.push((format!("{} (present)", ident), bit..bit, false));
                     }
+                    ranges
+                },
+                FieldKind::Array { ref ident, ref dims, ref elements } => {
+                    elements.iter().enumerate().map(|(i, field)| {
+                        (format!("{}{}", ident, array_indices_str(&array_indices(dims, i))), field.bit_range.clone(), field.overlay)
+                    }).collect()
+                }
+            }
+        };
 
-                    bits[i] = Some(name.clone());
+        // the regular fields own their bits
+        for (name, range, _) in fields_parsed.iter().flat_map(field_ranges).filter(|r| !r.2) {
+            for i in range.start .. (range.end+1) {
+                if let Some(Some(n)) = bits.get(i) {
+                    return Err(syn::Error::new(name.span(), format!("Overlap in bits between fields {} and {}", n, name)));
                 }
 
-                Ok(())
-            };
+                bits[i] = Some(name.clone());
+            }
+        }
 
-            match field {
-                FieldKind::Regular { ref field, ref ident } => {
-                    find_overlaps(ident.to_string(), &field.bit_range)?;
-                },
-                FieldKind::Array { ref ident, ref elements, .. } => {
-                    for (i, field) in elements.iter().enumerate() {
-                        find_overlaps(format!("{}[{}]", ident, i), &field.bit_range)?;
-                    }
+        // overlays only share bits that belong to regular fields, never with each other
+        for (name, range, _) in fields_parsed.iter().flat_map(field_ranges).filter(|r| r.2) {
+            for i in range.start .. (range.end+1) {
+                if let Some(Some(n)) = overlay_bits.get(i) {
+                    return Err(syn::Error::new(name.span(), format!("Overlap in bits between the overlays {} and {}", n, name)));
+                }
+                if !matches!(bits.get(i), Some(Some(_))) {
+                    return Err(syn::Error::new(name.span(), format!("The overlay {} has to be within the bits of other fields, bit {} doesn't belong to any.", name, i)));
                 }
+
+                overlay_bits[i] = Some(name.clone());
             }
         }
     }
     
+    for (field, parsed) in positioned_fields.iter().zip(fields_parsed.iter_mut()) {
+        parse_checksum(field, parsed, num_bytes, bit_positioning)?;
+    }
+
+    let mut dynamic_fields_parsed: Vec<FieldDynamic> = vec![];
+    for (i, field) in dynamic_fields.iter().enumerate() {
+        let dynamic = parse_dynamic_field(field, i == dynamic_fields.len() - 1, &fields_parsed, default_int_endianness)?;
+
+        if let DynamicLength::Count { field: ref count, .. } = dynamic.length {
+            if let Some(other) = dynamic_fields_parsed.iter().find(|d| matches!(d.length, DynamicLength::Count { field: ref c, .. } if c == count)) {
+                return Err(syn::Error::new(field.span(), format!("The count field {} is already used by the field {}.", count, other.ident)));
+            }
+        }
+
+        dynamic_fields_parsed.push(dynamic);
+    }
+
     Ok(PackStruct {
         derive_input: ast,
         data_struct,
         fields: fields_parsed,
+        inferred_fields,
+        dynamic_fields: dynamic_fields_parsed,
+        skipped_fields,
         num_bytes,
         num_bits
     })
 }
+
+#[test]
+fn test_parse_bool_patterns() {
+    assert_eq!(Ok(0xFFFF_FFFF_FFFF_FFFF), parse_u64("0xFFFFFFFFFFFFFFFF"));
+    assert_eq!(Ok(0b1010), parse_u64("0b10_10"));
+
+    let wide: syn::DeriveInput = syn::parse_quote! {
+        #[packed_struct(bit_numbering="msb0")]
+        struct Wide {
+            #[packed_field(bits="0..=3", true_value="0x1F", false_value="0")]
+            flag: bool
+        }
+    };
+    let err = parse_struct(&wide).err().expect("a pattern wider than its field");
+    assert!(err.to_string().contains("fit into 4 bits"));
+
+    let fits: syn::DeriveInput = syn::parse_quote! {
+        #[packed_struct(bit_numbering="msb0")]
+        struct Fits {
+            #[packed_field(bits="0..=63", true_value="0xFFFFFFFFFFFFFFFF", false_value="0")]
+            flag: bool
+        }
+    };
+    assert!(parse_struct(&fits).is_ok());
+}
@@ -73,9 +73,28 @@ pub enum PackFieldAttributeKind {
     BytePosition,
     ElementSizeBytes,
     ElementSizeBits,
+    ElementStrideBits,
     SizeBytes,
     SizeBits,
-    Ty
+    Ty,
+    Count,
+    LengthPrefix,
+    PresentBit,
+    NoneValue,
+    Condition,
+    Checksum,
+    ChecksumOver,
+    Parity,
+    ParityOver,
+    With,
+    Skip,
+    Default,
+    Flatten,
+    ElementOrder,
+    Overlay,
+    Invert,
+    TrueValue,
+    FalseValue
 }
 
 impl PackFieldAttributeKind {
@@ -90,7 +109,26 @@ impl PackFieldAttributeKind {
             SizeBits => "size_bits",
             ElementSizeBytes => "element_size_bytes",
             ElementSizeBits => "element_size_bits",
-            Ty => "ty"
+            ElementStrideBits => "element_stride_bits",
+            Ty => "ty",
+            Count => "count",
+            LengthPrefix => "length_prefix",
+            PresentBit => "present_bit",
+            NoneValue => "none_value",
+            Condition => "if",
+            Checksum => "checksum",
+            ChecksumOver => "over",
+            Parity => "parity",
+            ParityOver => "over_bits",
+            With => "with",
+            Skip => "skip",
+            Default => "default",
+            Flatten => "flatten",
+            ElementOrder => "element_order",
+            Overlay => "overlay",
+            Invert => "invert",
+            TrueValue => "true_value",
+            FalseValue => "false_value"
         }
     }
 }
@@ -101,13 +139,40 @@ pub enum PackFieldAttribute {
     BytePosition(BitsPositionParsed),
     SizeBits(usize),
     ElementSizeBits(usize),
-    Ty(TyKind)
+    ElementStrideBits(usize),
+    Ty(TyKind),
+    Count(String),
+    LengthPrefix(String),
+    PresentBit(usize),
+    NoneValue(String),
+    Condition(String),
+    Checksum(String),
+    ChecksumOver(String),
+    Parity(bool),
+    ParityOver(BitsPositionParsed),
+    With(String),
+    Skip,
+    Default(String),
+    Flatten,
+    ElementOrder(ElementOrder),
+    Overlay,
+    Invert,
+    TrueValue(u64),
+    FalseValue(u64)
 }
 
 pub enum TyKind {
     Enum
 }
 
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ElementOrder {
+    /// The first element at the lowest MSB0 bit
+    Forward,
+    /// The first element at the highest MSB0 bit
+    Reverse
+}
+
 impl PackFieldAttribute {
     pub fn parse(name: &str, val: &str) -> Result<Self, String> {
         if name == PackFieldAttributeKind::IntEndiannes.get_attr_name() {            
@@ -144,10 +209,99 @@ impl PackFieldAttribute {
             return Ok(PackFieldAttribute::ElementSizeBits(b));
         }
 
+        if name == PackFieldAttributeKind::ElementStrideBits.get_attr_name() {
+            let b = parse_num(val)?;
+            return Ok(PackFieldAttribute::ElementStrideBits(b));
+        }
+
         if name == PackFieldAttributeKind::Ty.get_attr_name() && val == "enum" {
             return Ok(PackFieldAttribute::Ty(TyKind::Enum));
         }
 
+        if name == PackFieldAttributeKind::Count.get_attr_name() {
+            return Ok(PackFieldAttribute::Count(val.trim().to_string()));
+        }
+
+        if name == PackFieldAttributeKind::LengthPrefix.get_attr_name() {
+            return Ok(PackFieldAttribute::LengthPrefix(val.trim().to_string()));
+        }
+
+        if name == PackFieldAttributeKind::PresentBit.get_attr_name() {
+            let b = parse_num(val)?;
+            return Ok(PackFieldAttribute::PresentBit(b));
+        }
+
+        if name == PackFieldAttributeKind::NoneValue.get_attr_name() {
+            return Ok(PackFieldAttribute::NoneValue(val.trim().to_string()));
+        }
+
+        if name == PackFieldAttributeKind::Condition.get_attr_name() {
+            return Ok(PackFieldAttribute::Condition(val.trim().to_string()));
+        }
+
+        if name == PackFieldAttributeKind::Checksum.get_attr_name() {
+            return Ok(PackFieldAttribute::Checksum(val.trim().to_string()));
+        }
+
+        if name == PackFieldAttributeKind::ChecksumOver.get_attr_name() {
+            return Ok(PackFieldAttribute::ChecksumOver(val.trim().to_string()));
+        }
+
+        if name == PackFieldAttributeKind::Parity.get_attr_name() {
+            return match val.trim() {
+                "even" => Ok(PackFieldAttribute::Parity(false)),
+                "odd" => Ok(PackFieldAttribute::Parity(true)),
+                _ => Err(format!("Invalid parity: {}, expected even or odd", val))
+            };
+        }
+
+        if name == PackFieldAttributeKind::ParityOver.get_attr_name() {
+            let b = parse_position_val(val, 1)?;
+            return Ok(PackFieldAttribute::ParityOver(b));
+        }
+
+        if name == PackFieldAttributeKind::With.get_attr_name() {
+            return Ok(PackFieldAttribute::With(val.trim().to_string()));
+        }
+
+        if name == PackFieldAttributeKind::Skip.get_attr_name() {
+            return Ok(PackFieldAttribute::Skip);
+        }
+
+        if name == PackFieldAttributeKind::Default.get_attr_name() {
+            return Ok(PackFieldAttribute::Default(val.trim().to_string()));
+        }
+
+        if name == PackFieldAttributeKind::Flatten.get_attr_name() {
+            return Ok(PackFieldAttribute::Flatten);
+        }
+
+        if name == PackFieldAttributeKind::Overlay.get_attr_name() {
+            return Ok(PackFieldAttribute::Overlay);
+        }
+
+        if name == PackFieldAttributeKind::Invert.get_attr_name() {
+            return Ok(PackFieldAttribute::Invert);
+        }
+
+        if name == PackFieldAttributeKind::TrueValue.get_attr_name() {
+            let v = parse_u64(val)?;
+            return Ok(PackFieldAttribute::TrueValue(v));
+        }
+
+        if name == PackFieldAttributeKind::FalseValue.get_attr_name() {
+            let v = parse_u64(val)?;
+            return Ok(PackFieldAttribute::FalseValue(v));
+        }
+
+        if name == PackFieldAttributeKind::ElementOrder.get_attr_name() {
+            return match val.trim() {
+                "forward" => Ok(PackFieldAttribute::ElementOrder(ElementOrder::Forward)),
+                "reverse" => Ok(PackFieldAttribute::ElementOrder(ElementOrder::Reverse)),
+                _ => Err(format!("Invalid element order: {}, expected forward or reverse", val))
+            };
+        }
+
         Err(format!("Unsupported field attribute: {}, value {}", name, val))
     }
 
@@ -243,6 +397,33 @@ pub fn parse_position_val(v: &str, multiplier: usize) -> Result<BitsPositionPars
 }
 
 
+/// A range of bytes, `0..10`, `0..=9` or the open ended `2..`. Returns the start and
+/// the exclusive end.
+pub fn parse_byte_range(v: &str) -> Result<(usize, Option<usize>), String> {
+    let v = v.trim();
+    if let Some(start) = v.strip_suffix("..") {
+        return Ok((parse_num(start)?, None));
+    }
+
+    if let Some((start, end)) = v.split_once("..=") {
+        return Ok((parse_num(start)?, Some(parse_num(end)? + 1)));
+    }
+
+    if let Some((start, end)) = v.split_once("..") {
+        return Ok((parse_num(start)?, Some(parse_num(end)?)));
+    }
+
+    Err(format!("Invalid range of bytes: '{}'", v))
+}
+
+#[test]
+fn test_parse_byte_range() {
+    assert_eq!(Ok((0, Some(10))), parse_byte_range("0..10"));
+    assert_eq!(Ok((1, Some(10))), parse_byte_range("1..=9"));
+    assert_eq!(Ok((2, None)), parse_byte_range("2.."));
+    assert!(parse_byte_range("5").is_err());
+}
+
 #[test]
 fn test_parse_position_val() {
     {
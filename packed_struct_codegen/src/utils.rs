@@ -28,6 +28,22 @@ impl BitsRange for NextBits {
 }
 
 
+/// The indices of the `i`-th element of an array with these dimensions, in row-major order.
+pub fn array_indices(dims: &[usize], mut i: usize) -> Vec<usize> {
+    let mut indices = vec![0; dims.len()];
+    for (index, dim) in indices.iter_mut().zip(dims).rev() {
+        *index = i % dim;
+        i /= dim;
+    }
+    indices
+}
+
+/// Like `[1][3]`
+pub fn array_indices_str(indices: &[usize]) -> String {
+    indices.iter().map(|i| format!("[{}]", i)).collect()
+}
+
+
 pub fn ones_u8(n: u8) -> u8 {    
     match n {
         0 => 0b00000000,
@@ -1,4 +1,4 @@
-use proc_macro2::TokenStream;
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
 use syn::{Error, PathSegment, Result, spanned::Spanned, TypePath};
 
 pub fn get_single_segment(type_path: &TypePath) -> Result<&PathSegment> {
@@ -23,4 +23,63 @@ pub fn tokens_to_string<T: quote::ToTokens>(t: &T) -> String {
     let mut tokens = TokenStream::new();
     t.to_tokens(&mut tokens);
     tokens.to_string()
-}
\ No newline at end of file
+}
+
+/// Turns `self.field` into `field`, to evaluate an expression against the already
+/// unpacked fields.
+pub fn strip_self(tokens: TokenStream) -> TokenStream {
+    let mut r = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ref ident) if ident == "self" => {
+                if let Some(TokenTree::Punct(punct)) = tokens.peek() {
+                    if punct.as_char() == '.' {
+                        tokens.next();
+                        continue;
+                    }
+                }
+                r.push(token);
+            },
+            TokenTree::Group(group) => {
+                let mut stripped = Group::new(group.delimiter(), strip_self(group.stream()));
+                stripped.set_span(group.span());
+                r.push(TokenTree::Group(stripped));
+            },
+            _ => r.push(token)
+        }
+    }
+    r.into_iter().collect()
+}
+
+/// Replaces `self.field` with the given expression, for the fields whose packed value
+/// differs from the one stored in the structure.
+pub fn replace_self_fields(tokens: TokenStream, replacements: &[(&syn::Ident, TokenStream)]) -> TokenStream {
+    let mut r = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ref ident) if ident == "self" => {
+                let mut lookahead = tokens.clone();
+                if let (Some(TokenTree::Punct(punct)), Some(TokenTree::Ident(field))) = (lookahead.next(), lookahead.next()) {
+                    if punct.as_char() == '.' {
+                        if let Some((_, replacement)) = replacements.iter().find(|(i, _)| **i == field) {
+                            tokens.next();
+                            tokens.next();
+                            r.push(TokenTree::Group(Group::new(Delimiter::Parenthesis, replacement.clone())));
+                            continue;
+                        }
+                    }
+                }
+                r.push(token);
+            },
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(group.delimiter(), replace_self_fields(group.stream(), replacements));
+                replaced.set_span(group.span());
+                r.push(TokenTree::Group(replaced));
+            },
+            _ => r.push(token)
+        }
+    }
+    r.into_iter().collect()
+}
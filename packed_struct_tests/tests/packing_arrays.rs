@@ -51,4 +51,122 @@ fn test_packed_array_of_structs() {
 
     let unpacked = Packaged::unpack(&packed).unwrap();
     assert_eq!(&p, &unpacked);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_packed_multidimensional_arrays() {
+
+    #[derive(PackedStruct, Debug, PartialEq, Eq)]
+    #[packed_struct(bit_numbering="msb0")]
+    pub struct Matrix {
+        #[packed_field(bits="0..=15")]
+        leds: [[bool; 8]; 2],
+        #[packed_field(bits="16..=39", element_size_bits="4")]
+        table: [[u8; 3]; 2]
+    }
+
+    let m = Matrix {
+        leds: [
+            [true, false, false, false, false, false, false, true],
+            [false, false, false, false, true, true, true, true]
+        ],
+        table: [[1, 2, 3], [4, 5, 6]]
+    };
+
+    let packed = m.pack().unwrap();
+    assert_eq!([0x81, 0x0F, 0x12, 0x34, 0x56], packed);
+    assert_eq!(m, Matrix::unpack(&packed).unwrap());
+
+    let fields = packed_struct::debug_fmt::PackedStructDebug::debug_fields(&m);
+    assert_eq!(("leds[1][3]".to_string(), 11..11), (fields[11].name.to_string(), fields[11].bits.clone()));
+    assert_eq!(("table[1][0]".to_string(), 28..31), (fields[19].name.to_string(), fields[19].bits.clone()));
+
+    #[derive(PackedStruct, Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct Pixel {
+        r: u8,
+        g: u8
+    }
+
+    #[derive(PackedStruct, Debug, PartialEq, Eq)]
+    pub struct Image {
+        pixels: [[Pixel; 2]; 2]
+    }
+
+    let p = |v: u8| Pixel { r: v, g: v + 1 };
+    let image = Image { pixels: [[p(1), p(3)], [p(5), p(7)]] };
+    let packed = image.pack().unwrap();
+    assert_eq!([1, 2, 3, 4, 5, 6, 7, 8], packed);
+    assert_eq!(image, Image::unpack(&packed).unwrap());
+}
+
+#[test]
+fn test_packed_arrays_reverse_order() {
+
+    #[derive(PackedStruct, Debug, PartialEq, Eq)]
+    #[packed_struct(size_bytes="2", bit_numbering="lsb0")]
+    pub struct Channels {
+        #[packed_field(bits="0..=7", element_order="reverse")]
+        enabled: [bool; 8],
+        #[packed_field(bits="8..=15", element_size_bits="2", element_order="reverse")]
+        gain: [u8; 4]
+    }
+
+    let c = Channels {
+        enabled: [true, true, false, false, false, false, false, false],
+        gain: [3, 0, 1, 2]
+    };
+
+    let packed = c.pack().unwrap();
+    assert_eq!([0b10_01_00_11, 0b0000_0011], packed);
+    assert_eq!(c, Channels::unpack(&packed).unwrap());
+
+    let fields = packed_struct::debug_fmt::PackedStructDebug::debug_fields(&c);
+    assert_eq!(("enabled[0]".to_string(), 15..15), (fields[0].name.to_string(), fields[0].bits.clone()));
+    assert_eq!(("gain[0]".to_string(), 6..7), (fields[8].name.to_string(), fields[8].bits.clone()));
+}
+
+#[test]
+fn test_packed_arrays_with_stride() {
+    #[derive(PackedStruct, Debug, PartialEq, Eq)]
+    #[packed_struct(size_bytes="2", bit_numbering="lsb0")]
+    pub struct Interleaved {
+        #[packed_field(bits="0..=12", element_stride_bits="4", element_order="reverse")]
+        enabled: [bool; 4],
+        #[packed_field(bits="1..=3")]
+        mode: Integer<u8, packed_bits::Bits::<3>>,
+        #[packed_field(bits="5..=7")]
+        gain: Integer<u8, packed_bits::Bits::<3>>,
+        #[packed_field(bits="9..=11")]
+        offset: Integer<u8, packed_bits::Bits::<3>>,
+        #[packed_field(bits="13..=15")]
+        filter: Integer<u8, packed_bits::Bits::<3>>
+    }
+
+    let i = Interleaved {
+        enabled: [true, false, true, true],
+        mode: 2.into(),
+        gain: 7.into(),
+        offset: 0.into(),
+        filter: 5.into()
+    };
+
+    let packed = i.pack().unwrap();
+    assert_eq!([0b1011_0001, 0b1110_0101], packed);
+    assert_eq!(i, Interleaved::unpack(&packed).unwrap());
+
+    #[derive(PackedStruct, Debug, PartialEq, Eq)]
+    #[packed_struct(bit_numbering="msb0")]
+    pub struct Nibbles {
+        #[packed_field(bits="0..", element_size_bits="4", element_stride_bits="8")]
+        high: [u8; 3],
+        #[packed_field(bits="4..=7")]
+        low: Integer<u8, packed_bits::Bits::<4>>,
+        #[packed_field(bits="20..=23")]
+        last: Integer<u8, packed_bits::Bits::<4>>
+    }
+
+    let n = Nibbles { high: [1, 2, 3], low: 4.into(), last: 5.into() };
+    let packed = n.pack().unwrap();
+    assert_eq!([0x14, 0x20, 0x35], packed);
+    assert_eq!(n, Nibbles::unpack(&packed).unwrap());
+}
@@ -0,0 +1,33 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct SafetyControl {
+    #[packed_field(bits="0", invert)]
+    reset: bool,
+    #[packed_field(bits="1..=3", invert)]
+    outputs: [bool; 3],
+    #[packed_field(bits="4..=7", true_value="0b1010", false_value="0b0101")]
+    armed: bool,
+    #[packed_field(bits="8..=23", true_value="0xA55A", false_value="0x5AA5")]
+    unlocked: bool
+}
+
+#[test]
+fn test_bool_patterns() {
+    let control = SafetyControl { reset: true, outputs: [false, true, false], armed: true, unlocked: false };
+    let packed = control.pack().unwrap();
+    assert_eq!([0b0101_1010, 0x5A, 0xA5], packed);
+    assert_eq!(control, SafetyControl::unpack(&packed).unwrap());
+
+    let control = SafetyControl { reset: false, outputs: [true, true, true], armed: false, unlocked: true };
+    let packed = control.pack().unwrap();
+    assert_eq!([0b1000_0101, 0xA5, 0x5A], packed);
+    assert_eq!(control, SafetyControl::unpack(&packed).unwrap());
+}
+
+#[test]
+fn test_bool_patterns_reject_other_values() {
+    assert_eq!(Err(PackingError::InvalidValue), SafetyControl::unpack(&[0b0000_1111, 0xA5, 0x5A]));
+    assert_eq!(Err(PackingError::InvalidValue), SafetyControl::unpack(&[0b0000_1010, 0xA5, 0x5B]));
+}
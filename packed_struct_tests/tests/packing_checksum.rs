@@ -0,0 +1,77 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="lsb")]
+pub struct ModbusFrame {
+    #[packed_field(bytes="0")]
+    address: u8,
+    #[packed_field(bytes="1")]
+    function: u8,
+    #[packed_field(bytes="2..=3", endian="msb")]
+    register: u16,
+    #[packed_field(bytes="4..=5", endian="msb")]
+    count: u16,
+    #[packed_field(bytes="6..=7", checksum="crc16_modbus")]
+    crc: u16
+}
+
+#[test]
+fn test_checksum_modbus() {
+    let frame = ModbusFrame { address: 0x01, function: 0x03, register: 0x0000, count: 0x000A, crc: 0 };
+    let packed = frame.pack().unwrap();
+    assert_eq!([0x01, 0x03, 0x00, 0x00, 0x00, 0x0A, 0xC5, 0xCD], packed);
+
+    let unpacked = ModbusFrame::unpack(&packed).unwrap();
+    assert_eq!(0xCDC5, unpacked.crc);
+
+    let mut corrupted = packed;
+    corrupted[7] ^= 1;
+    assert_eq!(Err(PackingError::ChecksumMismatch), ModbusFrame::unpack(&corrupted));
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Header {
+    #[packed_field(bits="0..=3", checksum="header_check", over="1..")]
+    check: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="4..=7")]
+    kind: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bytes="1..=2")]
+    payload: [u8; 2],
+    #[packed_field(bytes="3", checksum="xor8", over="1..=2")]
+    xor: u8
+}
+
+fn header_check(data: &[u8]) -> u8 {
+    packed_struct::checksum::sum8(data) & 0x0F
+}
+
+#[test]
+fn test_checksum_custom() {
+    let header = Header { check: 0.into(), kind: 2.into(), payload: [0x12, 0x34], xor: 0 };
+    let packed = header.pack().unwrap();
+    // the check covers the xor, so it's computed after it: 0x12 + 0x34 + 0x26 = 0x6C
+    assert_eq!([0xC2, 0x12, 0x34, 0x26], packed);
+    assert_eq!(0xC, *Header::unpack(&packed).unwrap().check);
+
+    assert_eq!(Err(PackingError::ChecksumMismatch), Header::unpack(&[0x72, 0x12, 0x34, 0x26]));
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Batch {
+    id: u16,
+    #[packed_field(checksum="crc32")]
+    crc: u32,
+    #[packed_field(length_prefix="u8")]
+    records: Vec<u8>
+}
+
+#[test]
+fn test_checksum_dynamic() {
+    let batch = Batch { id: 7, crc: 0, records: vec![1, 2] };
+    let packed = batch.pack_to_vec().unwrap();
+    let crc = packed_struct::checksum::crc32(&[0, 7]);
+    assert_eq!(crc.to_be_bytes(), packed[2..6]);
+    assert_eq!(crc, Batch::unpack_from_slice(&packed).unwrap().crc);
+}
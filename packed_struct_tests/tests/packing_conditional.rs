@@ -0,0 +1,96 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Extension {
+    flags: u8,
+    timestamp: u16
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Frame {
+    #[packed_field(bits="0..=3")]
+    version: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="4")]
+    has_crc: bool,
+    #[packed_field(bits="5..=7")]
+    _reserved: ReservedZero<packed_bits::Bits::<3>>,
+    #[packed_field(bytes="1")]
+    num_values: u8,
+    #[packed_field(if="*self.version >= 2")]
+    extension: Option<Extension>,
+    #[packed_field(count="num_values")]
+    values: Vec<u8>,
+    #[packed_field(if="self.has_crc && self.values.len() > 0")]
+    crc: Option<[u8; 2]>
+}
+
+#[test]
+fn test_conditional_fields() {
+    let v1 = Frame { version: 1.into(), has_crc: false, _reserved: Default::default(), num_values: 0, extension: None, values: vec![0xAA], crc: None };
+    let packed = v1.pack_to_vec().unwrap();
+    assert_eq!(&[0x10, 0x01, 0xAA][..], &packed[..]);
+    assert_eq!(v1, Frame::unpack_from_slice(&packed).unwrap().with_count(1));
+
+    let v2 = Frame {
+        version: 2.into(),
+        has_crc: true,
+        _reserved: Default::default(),
+        num_values: 0,
+        extension: Some(Extension { flags: 0x80, timestamp: 0x1234 }),
+        values: vec![0xAA, 0xBB],
+        crc: Some([0xC1, 0xC2])
+    };
+    assert_eq!(Ok(2 + 3 + 2 + 2), Frame::packed_bytes_size(Some(&v2)));
+    let packed = v2.pack_to_vec().unwrap();
+    assert_eq!(&[0x28, 0x02, 0x80, 0x12, 0x34, 0xAA, 0xBB, 0xC1, 0xC2][..], &packed[..]);
+    assert_eq!(v2, Frame::unpack_from_slice(&packed).unwrap().with_count(2));
+
+    // the extension is missing
+    assert_eq!(Err(PackingError::BufferTooSmall), Frame::unpack_from_slice(&[0x20, 0x00, 0x80]).map(|_| ()));
+}
+
+#[test]
+fn test_conditional_fields_mismatch() {
+    let missing = Frame { version: 2.into(), has_crc: false, _reserved: Default::default(), num_values: 0, extension: None, values: vec![], crc: None };
+    assert_eq!(Err(PackingError::InvalidValue), missing.pack_to_vec());
+
+    let unexpected = Frame { version: 1.into(), has_crc: false, _reserved: Default::default(), num_values: 0, extension: None, values: vec![], crc: Some([0, 0]) };
+    assert_eq!(Err(PackingError::InvalidValue), unexpected.pack_to_vec());
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+pub struct Samples {
+    num_values: u8,
+    #[packed_field(count="num_values")]
+    values: Vec<u8>,
+    #[packed_field(if="self.num_values > 0")]
+    crc: Option<u8>
+}
+
+#[test]
+fn test_conditional_on_count_field() {
+    // the condition sees the count that is written, not the stored one
+    let samples = Samples { num_values: 0, values: vec![1, 2], crc: Some(7) };
+    let packed = samples.pack_to_vec().unwrap();
+    assert_eq!(&[0x02, 0x01, 0x02, 0x07][..], &packed[..]);
+    assert_eq!(Samples { num_values: 2, ..samples }, Samples::unpack_from_slice(&packed).unwrap());
+
+    let missing = Samples { num_values: 0, values: vec![1, 2], crc: None };
+    assert_eq!(Err(PackingError::InvalidValue), missing.pack_to_vec());
+
+    let empty = Samples { num_values: 3, values: vec![], crc: None };
+    let packed = empty.pack_to_vec().unwrap();
+    assert_eq!(&[0x00][..], &packed[..]);
+    assert_eq!(Samples { num_values: 0, ..empty }, Samples::unpack_from_slice(&packed).unwrap());
+}
+
+impl Frame {
+    // the count is filled in when packing
+    fn with_count(mut self, n: u8) -> Self {
+        assert_eq!(n, self.num_values);
+        self.num_values = 0;
+        self
+    }
+}
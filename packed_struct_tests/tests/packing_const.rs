@@ -0,0 +1,44 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, Default, PartialEq)]
+#[packed_struct(endian="lsb")]
+pub struct Packet {
+    sync: Const<packed_bits::Bits::<16>, 0xAA55>,
+    version: ConstLsb<packed_bits::Bits::<16>, 0x0102>,
+    length: u16
+}
+
+#[test]
+fn test_const_fields() {
+    let packet = Packet { length: 0x0304, ..Default::default() };
+    let packed = packet.pack().unwrap();
+    assert_eq!([0xAA, 0x55, 0x02, 0x01, 0x04, 0x03], packed);
+    assert_eq!(packet, Packet::unpack(&packed).unwrap());
+
+    assert_eq!(Err(PackingError::ConstMismatch { expected: 0xAA55, actual: 0x55AA }), Packet::unpack(&[0x55, 0xAA, 0x02, 0x01, 0x04, 0x03]));
+    assert_eq!(Err(PackingError::ConstMismatch { expected: 0x0102, actual: 0x0103 }), Packet::unpack(&[0xAA, 0x55, 0x03, 0x01, 0x04, 0x03]));
+}
+
+#[test]
+fn test_const_debug() {
+    let packet = Packet::default();
+    assert_eq!("Constant - always 0xAA55", format!("{:?}", packet.sync));
+    assert_eq!(0x0102, packet.version.value());
+}
+
+#[derive(PackedStruct, Debug, Default, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="lsb")]
+pub struct Tagged {
+    #[packed_field(bits="0..=11")]
+    tag: ConstLsb<packed_bits::Bits::<12>, 0xABC>,
+    #[packed_field(bits="12..=23")]
+    value: Integer<u16, packed_bits::Bits::<12>>
+}
+
+#[test]
+fn test_const_lsb_partial_bytes() {
+    let tagged = Tagged { value: 0xABC.into(), ..Default::default() };
+    let packed = tagged.pack().unwrap();
+    assert_eq!([0xBC, 0xAB, 0xCA], packed);
+    assert_eq!(tagged, Tagged::unpack(&packed).unwrap());
+}
@@ -0,0 +1,140 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Entry {
+    id: u8,
+    value: u16
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="msb")]
+pub struct Message {
+    #[packed_field(bits="0..=3")]
+    version: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="4..=7")]
+    num_entries: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bytes="1")]
+    kind: u8,
+    #[packed_field(count="num_entries")]
+    entries: Vec<Entry>,
+    #[packed_field(length_prefix="u16")]
+    name: Vec<u8>,
+    trailer: Vec<u8>
+}
+
+#[test]
+fn test_dynamic_fields_roundtrip() {
+    let msg = Message {
+        version: 1.into(),
+        // filled in from the vector
+        num_entries: 0.into(),
+        kind: 0x10,
+        entries: vec![Entry { id: 1, value: 0x0203 }, Entry { id: 4, value: 0x0506 }],
+        name: b"abc".to_vec(),
+        trailer: vec![0xFF]
+    };
+
+    assert_eq!(Ok(2 + 6 + 2 + 3 + 1), Message::packed_bytes_size(Some(&msg)));
+    assert_eq!(Err(PackingError::InstanceRequiredForSize), Message::packed_bytes_size(None));
+
+    let packed = msg.pack_to_vec().unwrap();
+    assert_eq!(&[0x12, 0x10,
+                 0x01, 0x02, 0x03, 0x04, 0x05, 0x06,
+                 0x00, 0x03, b'a', b'b', b'c',
+                 0xFF][..], &packed[..]);
+
+    let unpacked = Message::unpack_from_slice(&packed).unwrap();
+    assert_eq!(2, *unpacked.num_entries);
+    assert_eq!(msg.entries, unpacked.entries);
+    assert_eq!(msg.name, unpacked.name);
+    assert_eq!(msg.trailer, unpacked.trailer);
+}
+
+#[test]
+fn test_dynamic_fields_errors() {
+    let msg = Message {
+        version: 1.into(),
+        num_entries: 0.into(),
+        kind: 0,
+        entries: vec![Entry { id: 0, value: 0 }; 16],
+        name: vec![],
+        trailer: vec![]
+    };
+    // 16 entries don't fit into the 4 bit count
+    assert_eq!(Err(PackingError::InvalidValue), msg.pack_to_vec());
+
+    let mut buf = [0; 4];
+    assert_eq!(Err(PackingError::BufferSizeMismatch { expected: 2 + 16 * 3 + 2, actual: 4 }), msg.pack_to_slice(&mut buf));
+
+    // the count promises more entries than there are
+    assert_eq!(Err(PackingError::BufferTooSmall), Message::unpack_from_slice(&[0x12, 0x10, 0x01, 0x02, 0x03]).map(|_| ()));
+    // the length prefix promises more bytes than there are
+    assert_eq!(Err(PackingError::BufferTooSmall), Message::unpack_from_slice(&[0x10, 0x10, 0x00, 0x04, 0x01]).map(|_| ()));
+    assert_eq!(Err(PackingError::BufferTooSmall), Message::unpack_from_slice(&[0x10]).map(|_| ()));
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(endian="lsb")]
+pub struct Records {
+    first: u8,
+    num_values: u16,
+    #[packed_field(count="num_values")]
+    values: Vec<Entry>
+}
+
+#[test]
+fn test_dynamic_fields_trailing_bytes() {
+    let records = Records { first: 0xAA, num_values: 0, values: vec![Entry { id: 7, value: 0x0809 }] };
+    let packed = records.pack_to_vec().unwrap();
+    assert_eq!(&[0xAA, 0x01, 0x00, 0x07, 0x08, 0x09][..], &packed[..]);
+
+    let unpacked = Records::unpack_from_slice(&packed).unwrap();
+    assert_eq!(1, unpacked.num_values);
+    assert_eq!(records.values, unpacked.values);
+
+    let mut longer = packed.clone();
+    longer.push(0);
+    assert_eq!(Err(PackingError::BufferSizeMismatch { expected: 6, actual: 7 }), Records::unpack_from_slice(&longer).map(|_| ()));
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+pub struct PayloadOnly {
+    #[packed_field(length_prefix="u8")]
+    payload: Vec<u8>
+}
+
+#[test]
+fn test_dynamic_fields_only() {
+    let p = PayloadOnly { payload: vec![1, 2, 3] };
+    let packed = p.pack_to_vec().unwrap();
+    assert_eq!(&[3, 1, 2, 3][..], &packed[..]);
+    assert_eq!(p, PayloadOnly::unpack_from_slice(&packed).unwrap());
+
+    let too_long = PayloadOnly { payload: vec![0; 256] };
+    assert_eq!(Err(PackingError::InvalidValue), too_long.pack_to_vec());
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Sample {
+    value: u16
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+pub struct Samples {
+    #[packed_field(length_prefix="u8")]
+    samples: Vec<Sample>
+}
+
+#[test]
+fn test_dynamic_fields_length_prefix_in_bytes() {
+    // the prefix counts bytes, like LengthPrefixed
+    let s = Samples { samples: vec![Sample { value: 0x0102 }, Sample { value: 0x0304 }] };
+    let packed = s.pack_to_vec().unwrap();
+    assert_eq!(&[4, 0x01, 0x02, 0x03, 0x04][..], &packed[..]);
+    assert_eq!(s, Samples::unpack_from_slice(&packed).unwrap());
+    assert_eq!(packed, packed_struct::types::LengthPrefixed::<u8, Vec<Sample>>::from(s.samples).pack_to_vec().unwrap());
+
+    assert!(Samples::unpack_from_slice(&[3, 0x01, 0x02, 0x03]).is_err());
+}
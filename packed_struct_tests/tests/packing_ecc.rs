@@ -0,0 +1,71 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Word {
+    #[packed_field(bits="0..=6")]
+    data: Integer<u8, packed_bits::Bits::<7>>,
+    #[packed_field(bits="7", parity="even")]
+    parity: bool,
+    #[packed_field(bits="8..=21")]
+    value: Hamming74<u8>,
+    #[packed_field(bits="22", parity="odd", over_bits="8..=21")]
+    value_parity: bool,
+    #[packed_field(bits="23")]
+    flag: bool
+}
+
+#[test]
+fn test_parity_bits() {
+    let word = Word { data: 0b101_0111.into(), parity: false, value: 0x3C.into(), value_parity: false, flag: true };
+    let packed = word.pack().unwrap();
+    // five ones in the data
+    assert_eq!(0b1010_1111, packed[0]);
+
+    let unpacked = Word::unpack(&packed).unwrap();
+    assert!(unpacked.parity);
+    assert_eq!(word.value, unpacked.value);
+    assert_eq!(0, unpacked.value.corrected_bits());
+
+    let ones = <Hamming74<u8>>::new(0x3C).pack().unwrap().iter().map(|b| b.count_ones()).sum::<u32>();
+    assert_eq!(ones % 2 == 0, unpacked.value_parity);
+
+    let mut corrupted = packed;
+    corrupted[0] ^= 0b0100_0000;
+    assert_eq!(Err(PackingError::ChecksumMismatch), Word::unpack(&corrupted));
+}
+
+#[test]
+fn test_parity_detects_corrected_value() {
+    let word = Word { data: 0.into(), parity: false, value: 0xA5.into(), value_parity: false, flag: false };
+    let mut packed = word.pack().unwrap();
+    // the parity over the codewords catches the flipped bit, even though the code could correct it
+    packed[1] ^= 0b0000_0100;
+    assert_eq!(Err(PackingError::ChecksumMismatch), Word::unpack(&packed));
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Telemetry {
+    id: u8,
+    temperature: Secded<u16>,
+    pressure: Hamming74<u32>
+}
+
+#[test]
+fn test_hamming_fields() {
+    let t = Telemetry { id: 1, temperature: 0x1234.into(), pressure: 0xDEAD_BEEF.into() };
+    let packed = t.pack().unwrap();
+    assert_eq!(1 + 4 + 7, packed.len());
+
+    let mut corrupted = packed;
+    corrupted[2] ^= 0x10;
+    corrupted[11] ^= 0x01;
+    let unpacked = Telemetry::unpack(&corrupted).unwrap();
+    assert_eq!(t, unpacked);
+    assert_eq!(1, unpacked.temperature.corrected_bits());
+    assert_eq!(1, unpacked.pressure.corrected_bits());
+
+    corrupted[2] ^= 0x20;
+    assert_eq!(Err(PackingError::UncorrectableBitErrors), Telemetry::unpack(&corrupted));
+}
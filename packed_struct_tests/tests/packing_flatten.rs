@@ -0,0 +1,92 @@
+use packed_struct::prelude::*;
+use packed_struct::debug_fmt::PackedStructDebug;
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Origin {
+    #[packed_field(bits="0..=2")]
+    priority: Integer<u8, packed_bits::Bits::<3>>,
+    #[packed_field(bits="3..=7")]
+    source: Integer<u8, packed_bits::Bits::<5>>
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Command {
+    #[packed_field(bits="0..=3")]
+    opcode: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="4..=11", flatten)]
+    origin: Origin,
+    #[packed_field(bits="12..=27", element_size_bits="8", flatten)]
+    hops: [Origin; 2],
+    #[packed_field(bits="28..=31")]
+    argument: Integer<u8, packed_bits::Bits::<4>>
+}
+
+#[test]
+fn test_flatten_fields() {
+    let origin = Origin { priority: 5.into(), source: 0x11.into() };
+    let cmd = Command { opcode: 0xA.into(), origin, hops: [origin, Origin { priority: 1.into(), source: 2.into() }], argument: 3.into() };
+    let packed = cmd.pack().unwrap();
+    assert_eq!([0xAB, 0x1B, 0x12, 0x23], packed);
+    assert_eq!(cmd, Command::unpack(&packed).unwrap());
+
+    let fields = cmd.debug_fields();
+    let names: Vec<_> = fields.iter().map(|f| (f.name.to_string(), f.bits.clone())).collect();
+    assert_eq!(vec![
+        ("opcode".to_string(), 0..3),
+        ("origin.priority".to_string(), 4..6),
+        ("origin.source".to_string(), 7..11),
+        ("hops[0].priority".to_string(), 12..14),
+        ("hops[0].source".to_string(), 15..19),
+        ("hops[1].priority".to_string(), 20..22),
+        ("hops[1].source".to_string(), 23..27),
+        ("argument".to_string(), 28..31)
+    ], names);
+
+    // the generated listing still has one entry per field
+    let own: [_; 5] = debug_fields_command(&cmd);
+    assert_eq!("origin", own[1].name);
+    assert_eq!(4..11, own[1].bits);
+
+    let display = format!("{}", cmd);
+    assert!(display.contains("origin.source | bits   7:11  | 0b10001"));
+}
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Level {
+    #[packed_field(bits="0..=2")]
+    unused: Integer<u8, packed_bits::Bits::<3>>,
+    #[packed_field(bits="3..=7")]
+    value: Integer<u8, packed_bits::Bits::<5>>
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Narrow {
+    #[packed_field(bits="0..=4", flatten)]
+    level: Level,
+    #[packed_field(bits="5..=8")]
+    kind: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="9..=15")]
+    rest: Integer<u8, packed_bits::Bits::<7>>
+}
+
+#[test]
+fn test_flatten_narrower_than_nested_bytes() {
+    let narrow = Narrow { kind: 1.into(), level: Level { unused: 0.into(), value: 0x13.into() }, rest: 2.into() };
+    let packed = narrow.pack().unwrap();
+    assert_eq!([0x98, 0x82], packed);
+    assert_eq!(narrow, Narrow::unpack(&packed).unwrap());
+
+    // only the bits that the field holds are shown
+    let fields = narrow.debug_fields();
+    let names: Vec<_> = fields.iter().map(|f| (f.name.to_string(), f.bits.clone())).collect();
+    assert_eq!(vec![
+        ("level.value".to_string(), 0..4),
+        ("kind".to_string(), 5..8),
+        ("rest".to_string(), 9..15)
+    ], names);
+    assert!(format!("{}", narrow).contains("level.value"));
+}
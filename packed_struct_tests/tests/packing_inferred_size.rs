@@ -0,0 +1,86 @@
+use packed_struct::prelude::*;
+use packed_struct::PackedStructInfo;
+use packed_struct::debug_fmt::PackedStructDebug;
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Duration {
+    minutes: u8,
+    seconds: u16
+}
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Flags {
+    #[packed_field(bits="0..=3")]
+    level: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="4")]
+    enabled: bool
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Record {
+    id: u8,
+    span: Duration,
+    #[packed_field(flatten)]
+    slots: [Flags; 2],
+    #[packed_field(size_bits="4")]
+    count: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(size_bits="4")]
+    kind: Integer<u8, packed_bits::Bits::<4>>,
+    checksum: u16
+}
+
+#[test]
+fn test_inferred_nested_sizes() {
+    let record = Record {
+        id: 0x11,
+        span: Duration { minutes: 2, seconds: 0x0304 },
+        slots: [Flags { level: 5.into(), enabled: true }, Flags { level: 1.into(), enabled: false }],
+        count: 6.into(),
+        kind: 7.into(),
+        checksum: 0xABCD
+    };
+
+    let packed = record.pack().unwrap();
+    assert_eq!([0x11, 0x02, 0x03, 0x04, 0x58, 0x10, 0x67, 0xAB, 0xCD], packed);
+    assert_eq!(record, Record::unpack(&packed).unwrap());
+    assert_eq!(72, Record::packed_bits());
+
+    let fields = record.debug_fields();
+    let names: Vec<_> = fields.iter().map(|f| (f.name.to_string(), f.bits.clone())).collect();
+    assert_eq!(vec![
+        ("id".to_string(), 0..7),
+        ("span".to_string(), 8..31),
+        ("slots[0].level".to_string(), 32..35),
+        ("slots[0].enabled".to_string(), 36..36),
+        ("slots[1].level".to_string(), 40..43),
+        ("slots[1].enabled".to_string(), 44..44),
+        ("count".to_string(), 48..51),
+        ("kind".to_string(), 52..55),
+        ("checksum".to_string(), 56..71)
+    ], names);
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+pub struct Packet {
+    header: Duration,
+    #[packed_field(size_bytes="3")]
+    footer: Duration,
+    #[packed_field(length_prefix="u8")]
+    payload: Vec<u8>
+}
+
+#[test]
+fn test_inferred_with_dynamic_fields() {
+    let packet = Packet {
+        header: Duration { minutes: 1, seconds: 2 },
+        footer: Duration { minutes: 3, seconds: 4 },
+        payload: vec![0xAA, 0xBB]
+    };
+
+    let packed = packet.pack_to_vec().unwrap();
+    assert_eq!(&[0x01, 0x00, 0x02, 0x03, 0x00, 0x04, 0x02, 0xAA, 0xBB], &packed[..]);
+    assert_eq!(packet, Packet::unpack_from_slice(&packed).unwrap());
+}
@@ -1,6 +1,7 @@
 #[macro_use]
 extern crate error_chain;
 
+#[allow(unexpected_cfgs)]
 mod errors {
     error_chain! {
         foreign_links {
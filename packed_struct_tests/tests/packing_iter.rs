@@ -0,0 +1,53 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(endian="lsb")]
+pub struct LogRecord {
+    timestamp: u32,
+    level: u8
+}
+
+fn records(n: u32) -> Vec<u8> {
+    (0..n).flat_map(|i| LogRecord { timestamp: i * 10, level: i as u8 }.pack().unwrap().to_vec()).collect()
+}
+
+#[test]
+fn test_unpack_iter() {
+    let raw = records(100);
+    let iter = LogRecord::unpack_iter(&raw);
+    assert_eq!(100, iter.len());
+
+    for (i, r) in iter.enumerate() {
+        assert_eq!(LogRecord { timestamp: i as u32 * 10, level: i as u8 }, r.unwrap());
+    }
+
+    let unpacked: Result<Vec<_>, _> = LogRecord::unpack_iter(&raw).collect();
+    assert_eq!(unpacked.unwrap(), Vec::<LogRecord>::unpack_from_slice(&raw).unwrap());
+    assert_eq!(0, LogRecord::unpack_iter(&[]).count());
+}
+
+#[test]
+fn test_unpack_iter_trailing() {
+    let mut raw = records(3);
+    raw.extend_from_slice(&[0xAA, 0xBB]);
+
+    let unpacked: Vec<_> = LogRecord::unpack_iter(&raw).collect();
+    assert_eq!(4, unpacked.len());
+    assert!(unpacked[..3].iter().all(|r| r.is_ok()));
+    assert_eq!(Err(PackingError::BufferModMismatch { actual_size: 17, modulo_required: 5 }), unpacked[3]);
+
+    let mut iter = LogRecord::unpack_iter_exact(&raw);
+    assert_eq!(&[0xAA, 0xBB], iter.remainder());
+    assert_eq!(3, iter.by_ref().filter(|r| r.is_ok()).count());
+    assert_eq!(None, iter.next());
+}
+
+#[test]
+fn test_unpack_iter_errors() {
+    // invalid boolean in the middle doesn't stop the iteration
+    let results: Vec<_> = bool::unpack_iter(&[1, 2, 0]).collect();
+    assert_eq!(vec![Ok(true), Err(PackingError::InvalidValue), Ok(false)], results);
+
+    let results: Vec<_> = Vec::<u8>::unpack_iter(&[1, 2]).collect();
+    assert_eq!(vec![Err(PackingError::InstanceRequiredForSize)], results);
+}
@@ -0,0 +1,59 @@
+use packed_struct::prelude::*;
+
+#[derive(PrimitiveEnum_u8, Debug, Copy, Clone, PartialEq)]
+pub enum Mode {
+    Off = 0,
+    On = 1,
+    Auto = 2
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="msb")]
+pub struct Register {
+    #[packed_field(bits="1..=7", present_bit="0")]
+    level: Option<Integer<u8, packed_bits::Bits::<7>>>,
+    #[packed_field(bytes="1..=2", none_value="0xFFFF")]
+    threshold: Option<u16>,
+    #[packed_field(bits="24..=27", ty="enum", none_value="0xF")]
+    mode: Option<Mode>,
+    #[packed_field(bits="28..=31")]
+    _reserved: ReservedZero<packed_bits::Bits::<4>>
+}
+
+#[test]
+fn test_optional_fields() {
+    let r = Register { level: Some(0x55.into()), threshold: Some(0x1234), mode: Some(Mode::Auto), _reserved: Default::default() };
+    let packed = r.pack().unwrap();
+    assert_eq!([0xD5, 0x12, 0x34, 0x20], packed);
+    assert_eq!(r, Register::unpack(&packed).unwrap());
+
+    let r = Register { level: None, threshold: None, mode: None, _reserved: Default::default() };
+    let packed = r.pack().unwrap();
+    assert_eq!([0x00, 0xFF, 0xFF, 0xF0], packed);
+    assert_eq!(r, Register::unpack(&packed).unwrap());
+
+    // the payload is ignored without the flag
+    assert_eq!(None, Register::unpack(&[0x7F, 0, 0, 0]).unwrap().level);
+}
+
+#[test]
+fn test_optional_sentinel_conflict() {
+    let r = Register { level: None, threshold: Some(0xFFFF), mode: None, _reserved: Default::default() };
+    assert_eq!(Err(PackingError::InvalidValue), r.pack());
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="lsb0", size_bytes="2", endian="msb")]
+pub struct LsbRegister {
+    #[packed_field(bits="0..=11", present_bit="15")]
+    value: Option<Integer<u16, packed_bits::Bits::<12>>>
+}
+
+#[test]
+fn test_optional_present_bit_lsb0() {
+    let r = LsbRegister { value: Some(0x123.into()) };
+    let packed = r.pack().unwrap();
+    assert_eq!(0x8123, u16::from_be_bytes(packed));
+    assert_eq!(r, LsbRegister::unpack(&packed).unwrap());
+    assert_eq!(LsbRegister { value: None }, LsbRegister::unpack(&[0x01, 0x23]).unwrap());
+}
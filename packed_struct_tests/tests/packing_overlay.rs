@@ -0,0 +1,37 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="msb")]
+pub struct Register {
+    #[packed_field(bytes="0..=1")]
+    raw: u16,
+    #[packed_field(bytes="0..=1", overlay)]
+    parts: [u8; 2],
+    #[packed_field(bytes="2")]
+    mode: u8,
+    #[packed_field(bits="20..=23", overlay)]
+    channel: Integer<u8, packed_bits::Bits::<4>>
+}
+
+#[test]
+fn test_overlay_views() {
+    let unpacked = Register::unpack(&[0x12, 0x34, 0x84]).unwrap();
+    assert_eq!(0x1234, unpacked.raw);
+    assert_eq!([0x12, 0x34], unpacked.parts);
+    assert_eq!(0x84, unpacked.mode);
+    assert_eq!(4, *unpacked.channel);
+}
+
+#[test]
+fn test_overlay_pack_mismatch() {
+    let reg = Register { raw: 0x1234, parts: [0x12, 0x34], mode: 0x84, channel: 4.into() };
+    assert_eq!([0x12, 0x34, 0x84], reg.pack().unwrap());
+    assert_eq!(reg, Register::unpack(&reg.pack().unwrap()).unwrap());
+
+    // a stale view isn't packed silently
+    let stale = Register { raw: 0xFFFF, ..reg };
+    assert_eq!(Err(PackingError::OverlayMismatch), stale.pack());
+
+    let stale = Register { raw: 0x1234, parts: [0x12, 0x34], mode: 0x84, channel: 5.into() };
+    assert_eq!(Err(PackingError::OverlayMismatch), stale.pack());
+}
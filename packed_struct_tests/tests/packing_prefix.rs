@@ -0,0 +1,60 @@
+use packed_struct::prelude::*;
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="msb")]
+pub struct Header {
+    #[packed_field(bits="0..=3")]
+    version: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="4..=7")]
+    flags: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bytes="1..=2")]
+    payload_len: u16
+}
+
+#[test]
+fn test_unpack_from_prefix() {
+    let raw = [0x21, 0x00, 0x03, 0xAA, 0xBB, 0xCC];
+    let (header, payload) = Header::unpack_from_prefix(&raw).unwrap();
+    assert_eq!(2, *header.version);
+    assert_eq!(1, *header.flags);
+    assert_eq!(3, header.payload_len);
+    assert_eq!(&[0xAA, 0xBB, 0xCC], payload);
+
+    assert_eq!(Err(PackingError::BufferTooSmall), Header::unpack_from_prefix(&raw[..2]).map(|_| ()));
+
+    let (arr, rest) = <[u8; 2]>::unpack_from_prefix(&raw).unwrap();
+    assert_eq!([0x21, 0x00], arr);
+    assert_eq!(4, rest.len());
+
+    let (tuple, rest) = <(Header, u8)>::unpack_from_prefix(&raw).unwrap();
+    assert_eq!((header, 0xAA), tuple);
+    assert_eq!(&[0xBB, 0xCC], rest);
+
+    // dynamically sized members consume the rest
+    let (tuple, rest) = <(Header, Vec<u8>)>::unpack_from_prefix(&raw).unwrap();
+    assert_eq!(vec![0xAA, 0xBB, 0xCC], tuple.1);
+    assert!(rest.is_empty());
+}
+
+#[test]
+fn test_unpack_vec_from_prefix() {
+    let raw = [0x10, 0x00, 0x01, 0x20, 0x00, 0x02, 0x30];
+    let (headers, rest) = Vec::<Header>::unpack_from_prefix(&raw).unwrap();
+    assert_eq!(2, headers.len());
+    assert_eq!(2, headers[1].payload_len);
+    assert_eq!(&[0x30], rest);
+}
+
+#[test]
+fn test_pack_to_prefix() {
+    let header = Header { version: 1.into(), flags: 0.into(), payload_len: 2 };
+
+    let mut buf = [0; 8];
+    let rest = header.pack_to_prefix(&mut buf).unwrap();
+    let rest = vec![0xDD_u8, 0xEE].pack_to_prefix(rest).unwrap();
+    assert_eq!(3, rest.len());
+    assert_eq!([0x10, 0x00, 0x02, 0xDD, 0xEE, 0, 0, 0], buf);
+
+    let mut small = [0; 2];
+    assert_eq!(Err(PackingError::BufferTooSmall), header.pack_to_prefix(&mut small).map(|_| ()));
+}
@@ -21,3 +21,33 @@ fn test_packed_reserved_fields() {
     let unpacked = StructOne::unpack(&[0b1111_1000]).unwrap();
     assert!(unpacked.bool1);
 }
+
+#[derive(PackedStruct, Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct StructStrict {
+    #[packed_field(bits="0:3")]
+    pub _reserved1: ReservedZeroStrict<packed_bits::Bits::<4>>,
+    #[packed_field(bits="4")]
+    pub bool1: bool,
+    #[packed_field(bits="5:7")]
+    pub _reserved2: ReservedOneStrict<packed_bits::Bits::<3>>,
+    #[packed_field(bits="8:19")]
+    pub _reserved3: ReservedPreserve<packed_bits::Bits::<12>>,
+    #[packed_field(bits="20:23")]
+    pub value: Integer<u8, packed_bits::Bits::<4>>
+}
+
+#[test]
+fn test_packed_reserved_strict_fields() {
+    let s = StructStrict::default();
+    assert_eq!([0b0000_0111, 0, 0], s.pack().unwrap());
+
+    let unpacked = StructStrict::unpack(&[0b0000_1111, 0xAB, 0xC5]).unwrap();
+    assert!(unpacked.bool1);
+    assert_eq!(5, *unpacked.value);
+    assert_eq!(&[0x0A, 0xBC], unpacked._reserved3.raw());
+    assert_eq!([0b0000_1111, 0xAB, 0xC5], unpacked.pack().unwrap());
+
+    assert_eq!(Err(PackingError::ReservedBitsMismatch), StructStrict::unpack(&[0b0100_0111, 0, 0]));
+    assert_eq!(Err(PackingError::ReservedBitsMismatch), StructStrict::unpack(&[0b0000_0110, 0, 0]));
+}
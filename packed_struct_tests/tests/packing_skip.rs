@@ -0,0 +1,57 @@
+use packed_struct::prelude::*;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct Source {
+    address: u8,
+    port: u16
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="msb")]
+pub struct Sample {
+    #[packed_field(skip)]
+    source: Source,
+    #[packed_field(bits="0..=15")]
+    raw: u16,
+    #[packed_field(skip, default="raw as f32 / 100.0")]
+    scaled: f32,
+    #[packed_field(skip, default="Some(\"unpacked\")")]
+    origin: Option<&'static str>,
+    #[packed_field(bits="16..=23")]
+    flags: u8
+}
+
+#[test]
+fn test_skipped_fields() {
+    let sample = Sample { source: Source { address: 1, port: 80 }, raw: 1234, scaled: 0.0, origin: None, flags: 0xAA };
+    let packed = sample.pack().unwrap();
+    assert_eq!([0x04, 0xD2, 0xAA], packed);
+    assert_eq!(3, Sample::packed_bytes_size(None).unwrap());
+
+    let unpacked = Sample::unpack(&packed).unwrap();
+    assert_eq!(Source::default(), unpacked.source);
+    assert_eq!(1234, unpacked.raw);
+    assert_eq!(12.34, unpacked.scaled);
+    assert_eq!(Some("unpacked"), unpacked.origin);
+    assert_eq!(0xAA, unpacked.flags);
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+pub struct Datagram {
+    #[packed_field(skip)]
+    received_at: u64,
+    kind: u8,
+    #[packed_field(length_prefix="u8")]
+    payload: Vec<u8>
+}
+
+#[test]
+fn test_skipped_fields_dynamic() {
+    let datagram = Datagram { received_at: 1000, kind: 2, payload: vec![1, 2, 3] };
+    let packed = datagram.pack_to_vec().unwrap();
+    assert_eq!(&[2, 3, 1, 2, 3][..], &packed[..]);
+
+    let unpacked = Datagram::unpack_from_slice(&packed).unwrap();
+    assert_eq!(0, unpacked.received_at);
+    assert_eq!(datagram.payload, unpacked.payload);
+}
@@ -0,0 +1,69 @@
+use packed_struct::prelude::*;
+
+type AsciiName = PackedString<SpacePadded, Ascii, RejectOverflow, 6>;
+type Latin1Name = PackedString<NullPadded, Latin1, TruncateOverflow, 4>;
+
+#[test]
+fn test_fixed_string_padding() {
+    let s = FixedString::<6>::new("abc").unwrap();
+    assert_eq!(*b"abc\0\0\0", s.pack().unwrap());
+    assert_eq!(s, FixedString::<6>::unpack(b"abc\0xy").unwrap());
+    assert_eq!("", FixedString::<6>::unpack(&[0; 6]).unwrap());
+    assert_eq!("abcdef", FixedString::<6>::unpack(b"abcdef").unwrap());
+
+    let s = AsciiName::new("ab").unwrap();
+    assert_eq!(*b"ab    ", s.pack().unwrap());
+    let unpacked = AsciiName::unpack(b" a b  ").unwrap();
+    assert_eq!(" a b", unpacked);
+    assert_eq!(Some(" a b"), unpacked.as_str());
+}
+
+#[test]
+fn test_fixed_string_encodings() {
+    // two bytes in UTF-8
+    let s = FixedString::<4>::new("dé").unwrap();
+    assert_eq!(3, s.len());
+    assert_eq!(*b"d\xC3\xA9\0", s.pack().unwrap());
+    assert_eq!(Err(PackingError::InvalidValue), FixedString::<4>::unpack(b"d\xC3\0\0"));
+
+    assert_eq!(Err(PackingError::InvalidValue), AsciiName::new("dé"));
+    assert_eq!(Err(PackingError::InvalidValue), AsciiName::unpack(b"d\xE9    "));
+
+    let s = Latin1Name::new("dé").unwrap();
+    assert_eq!(*b"d\xE9\0\0", s.pack().unwrap());
+    let unpacked = Latin1Name::unpack(b"d\xE9\0\0").unwrap();
+    assert_eq!("dé", unpacked);
+    assert_eq!(None, unpacked.as_str());
+    assert_eq!(Err(PackingError::InvalidValue), Latin1Name::new("€"));
+}
+
+#[test]
+fn test_fixed_string_overflow() {
+    assert_eq!(Err(PackingError::CapacityExceeded { capacity: 6, required: 7 }), AsciiName::new("abcdefg"));
+    assert_eq!(Err(PackingError::CapacityExceeded { capacity: 4, required: 5 }), FixedString::<4>::new("abcé"));
+
+    assert_eq!("abcd", Latin1Name::new("abcdefg").unwrap());
+    // a multi-byte character isn't split
+    let s = PackedString::<NullPadded, Utf8, TruncateOverflow, 4>::new("abcé").unwrap();
+    assert_eq!("abc", s);
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Version {
+    id: u16,
+    firmware: FixedString<8>,
+    // aliases need an explicit size
+    #[packed_field(size_bytes="6")]
+    vendor: AsciiName
+}
+
+#[test]
+fn test_fixed_string_fields() {
+    let v = Version { id: 0x0102, firmware: FixedString::new("1.2.3").unwrap(), vendor: AsciiName::new("ACME").unwrap() };
+    let packed = v.pack().unwrap();
+    assert_eq!(*b"\x01\x021.2.3\0\0\0ACME  ", packed);
+    assert_eq!(v, Version::unpack(&packed).unwrap());
+    assert_eq!("\"1.2.3\"", format!("{:?}", v.firmware));
+    assert_eq!("ACME", format!("{}", v.vendor));
+}
@@ -19,3 +19,79 @@ fn test_tuple_undersized() {
     let res = T::unpack_from_slice(&p);
     assert!(res.is_err());    
 }
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Header {
+    kind: u8,
+    id: u16
+}
+
+type Length = MsbInteger<u16, packed_bits::Bits::<16>, Integer<u16, packed_bits::Bits::<16>>>;
+
+#[test]
+fn test_tuple_length_prefixed() {
+    type Message = (Header, LengthPrefixed<u8, Vec<u8>>, u8, LengthPrefixed<Length, Vec<u8>>);
+
+    let msg: Message = (Header { kind: 1, id: 0x0203 }, vec![0xA1, 0xA2].into(), 0xEE, vec![0xB1].into());
+    assert_eq!(Ok(3 + 3 + 1 + 3), Message::packed_bytes_size(Some(&msg)));
+
+    let packed = msg.pack_to_vec().unwrap();
+    assert_eq!(&[0x01, 0x02, 0x03, 0x02, 0xA1, 0xA2, 0xEE, 0x00, 0x01, 0xB1][..], &packed[..]);
+    assert_eq!(msg, Message::unpack_from_slice(&packed).unwrap());
+
+    // the second length points past the end of the buffer
+    assert_eq!(Err(PackingError::BufferSizeMismatch { expected: 11, actual: 10 }),
+        Message::unpack_from_slice(&[0x01, 0x02, 0x03, 0x02, 0xA1, 0xA2, 0xEE, 0x00, 0x02, 0xB1]).map(|_| ()));
+    // trailing bytes
+    assert_eq!(Err(PackingError::BufferSizeMismatch { expected: 10, actual: 11 }),
+        Message::unpack_from_slice(&[0x01, 0x02, 0x03, 0x02, 0xA1, 0xA2, 0xEE, 0x00, 0x01, 0xB1, 0xFF]).map(|_| ()));
+}
+
+#[test]
+fn test_tuple_length_prefixed_and_remainder() {
+    type Message = (LengthPrefixed<u8, Vec<u8>>, Vec<u8>, Header);
+
+    let raw = [0x01, 0xAA, 0xB1, 0xB2, 0xB3, 0x01, 0x02, 0x03];
+    let (a, b, header) = Message::unpack_from_slice(&raw).unwrap();
+    assert_eq!(vec![0xAA], a.into_inner());
+    assert_eq!(vec![0xB1, 0xB2, 0xB3], b);
+    assert_eq!(Header { kind: 1, id: 0x0203 }, header);
+
+    let prefixed: LengthPrefixed<u8, Vec<u8>> = vec![0; 256].into();
+    assert_eq!(Err(PackingError::InvalidValue), prefixed.pack_to_vec());
+}
+
+#[test]
+fn test_tuple_multiple_dynamic() {
+    type Message = (u8, Vec<u8>, LengthPrefixed<u8, Vec<u8>>);
+    assert_eq!(Err(PackingError::MoreThanOneDynamicType), Message::unpack_from_slice(&[0, 1, 2, 3]).map(|_| ()));
+
+    type Message2 = (Vec<u8>, Vec<u8>);
+    assert_eq!(Err(PackingError::MoreThanOneDynamicType), Message2::unpack_from_slice(&[0, 1, 2, 3]).map(|_| ()));
+}
+
+#[test]
+fn test_tuple_long() {
+    type Message = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, Vec<u8>);
+
+    let raw: Vec<u8> = (0..24).collect();
+    let unpacked = Message::unpack_from_slice(&raw).unwrap();
+    assert_eq!(19, unpacked.19);
+    assert_eq!(vec![20, 21, 22, 23], unpacked.20);
+    assert_eq!(raw, unpacked.pack_to_vec().unwrap());
+}
+
+#[test]
+fn test_tuple_nested_beyond_limit() {
+    // 24 members at most, longer messages nest tuples
+    type Half = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+    type Message = (Half, Half, Vec<u8>);
+
+    let raw: Vec<u8> = (0..40).collect();
+    let unpacked = Message::unpack_from_slice(&raw).unwrap();
+    assert_eq!(15, (unpacked.0).15);
+    assert_eq!(31, (unpacked.1).15);
+    assert_eq!(vec![32, 33, 34, 35, 36, 37, 38, 39], unpacked.2);
+    assert_eq!(raw, unpacked.pack_to_vec().unwrap());
+}
@@ -0,0 +1,65 @@
+use packed_struct::prelude::*;
+
+/// Two decimal digits, one in each nibble.
+mod bcd {
+    use packed_struct::{PackingError, PackingResult};
+
+    pub fn pack(value: &u8) -> PackingResult<[u8; 1]> {
+        if *value > 99 {
+            return Err(PackingError::InvalidValue);
+        }
+        Ok([((value / 10) << 4) | (value % 10)])
+    }
+
+    pub fn unpack(bytes: &[u8; 1]) -> PackingResult<u8> {
+        let (tens, ones) = (bytes[0] >> 4, bytes[0] & 0x0F);
+        if tens > 9 || ones > 9 {
+            return Err(PackingError::InvalidValue);
+        }
+        Ok(tens * 10 + ones)
+    }
+}
+
+/// Tenths of a degree in 12 bits, offset by 50 degrees.
+mod temperature {
+    use packed_struct::PackingResult;
+
+    pub fn pack(value: &f32) -> PackingResult<[u8; 2]> {
+        let raw = ((value + 50.0) * 10.0) as u16;
+        Ok(raw.to_be_bytes())
+    }
+
+    pub fn unpack(bytes: &[u8; 2]) -> PackingResult<f32> {
+        Ok(u16::from_be_bytes(*bytes) as f32 / 10.0 - 50.0)
+    }
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(bit_numbering="msb0")]
+pub struct Reading {
+    #[packed_field(bits="0..=7", with="bcd")]
+    hour: u8,
+    #[packed_field(bits="8..=15", with="bcd")]
+    minute: u8,
+    #[packed_field(bits="16..=19")]
+    sensor: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="20..=31", with="temperature")]
+    temperature: f32,
+    #[packed_field(bits="32..=47", element_size_bits="8", with="bcd")]
+    day_month: [u8; 2],
+    #[packed_field(bits="48..=55", with="bcd", present_bit="56")]
+    seconds: Option<u8>
+}
+
+#[test]
+fn test_with_module() {
+    let reading = Reading { hour: 23, minute: 59, sensor: 3.into(), temperature: 21.5, day_month: [31, 12], seconds: Some(7) };
+    let packed = reading.pack().unwrap();
+    // 715 = 0x2CB
+    assert_eq!([0x23, 0x59, 0x32, 0xCB, 0x31, 0x12, 0x07, 0x80], packed);
+    assert_eq!(reading, Reading::unpack(&packed).unwrap());
+
+    let invalid = Reading { hour: 100, ..reading };
+    assert_eq!(Err(PackingError::InvalidValue), invalid.pack());
+    assert_eq!(Err(PackingError::InvalidValue), Reading::unpack(&[0x2A, 0x59, 0x32, 0xCB, 0x31, 0x12, 0x07, 0x80]));
+}
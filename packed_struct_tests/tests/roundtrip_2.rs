@@ -4,6 +4,7 @@ mod common;
 use common::*;
 
 #[test]
+#[allow(clippy::legacy_numeric_constants, clippy::manual_is_multiple_of)]
 fn test_roundtrip_2() {
 
     #[derive(PackedStruct, Debug, PartialEq, Eq)]
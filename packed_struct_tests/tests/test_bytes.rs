@@ -0,0 +1,74 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use packed_struct::prelude::*;
+use packed_struct::io_bytes::{BufPacked, BufMutPacked, UnpackBytes};
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Header {
+    kind: u8,
+    length: u32
+}
+
+#[test]
+fn test_bytes_roundtrip() {
+    let header = Header { kind: 0x11, length: 0x22334455 };
+
+    let mut buf = BytesMut::new();
+    buf.put_packed(&header).unwrap();
+    buf.put_u8(0xFF);
+    assert_eq!(&[0x11, 0x22, 0x33, 0x44, 0x55, 0xFF], &buf[..]);
+
+    let mut buf = buf.freeze();
+    assert_eq!(header, buf.get_packed().unwrap());
+    assert_eq!(0xFF, buf.get_u8());
+    assert_eq!(Err(PackingError::BufferTooSmall), buf.get_packed::<Header>());
+}
+
+#[test]
+fn test_bytes_non_contiguous() {
+    // the header is split across both halves of the chain
+    let mut chained = (&[0x11, 0x22, 0x33][..]).chain(&[0x44, 0x55, 0x66][..]);
+    let header: Header = chained.get_packed().unwrap();
+    assert_eq!(Header { kind: 0x11, length: 0x22334455 }, header);
+    assert_eq!(1, chained.remaining());
+
+    let mut out = [0; 3];
+    let mut short = &mut out[..];
+    assert_eq!(Err(PackingError::BufferTooSmall), short.put_packed(&header));
+    assert_eq!(3, short.remaining_mut());
+}
+
+#[test]
+fn test_bytes_tuple_payload() {
+    type Message = (Header, Bytes, u8);
+
+    let raw = [0x01, 0x00, 0x00, 0x00, 0x03, 0xA0, 0xA1, 0xA2, 0xEE];
+    let unpacked = Message::unpack_from_slice(&raw).unwrap();
+    assert_eq!(Header { kind: 1, length: 3 }, unpacked.0);
+    assert_eq!(&[0xA0, 0xA1, 0xA2], &unpacked.1[..]);
+    assert_eq!(0xEE, unpacked.2);
+    assert_eq!(&raw[..], &unpacked.pack_to_vec().unwrap()[..]);
+
+    let unpacked = <(Header, BytesMut)>::unpack_from_slice(&raw).unwrap();
+    assert_eq!(&[0xA0, 0xA1, 0xA2, 0xEE], &unpacked.1[..]);
+}
+
+#[test]
+fn test_bytes_tuple_payload_shared() {
+    let frame = Bytes::from(vec![0x01, 0x00, 0x00, 0x00, 0x03, 0xA0, 0xA1, 0xA2, 0xEE]);
+    let source = frame.as_ptr_range();
+
+    let (header, payload) = <(Header, Bytes)>::unpack_from_bytes(frame.clone()).unwrap();
+    assert_eq!(Header { kind: 1, length: 3 }, header);
+    assert_eq!(&[0xA0, 0xA1, 0xA2, 0xEE], &payload[..]);
+    // points into the frame instead of a copy
+    assert_eq!(unsafe { source.start.add(5) }, payload.as_ptr());
+
+    let (header, payload, trailer) = <(Header, Bytes, u8)>::unpack_from_bytes(frame.clone()).unwrap();
+    assert_eq!(Header { kind: 1, length: 3 }, header);
+    assert_eq!(&[0xA0, 0xA1, 0xA2], &payload[..]);
+    assert_eq!(0xEE, trailer);
+    assert_eq!(unsafe { source.start.add(5) }, payload.as_ptr());
+
+    assert_eq!(Err(PackingError::BufferTooSmall), <(Header, Bytes)>::unpack_from_bytes(frame.slice(..3)).map(|_| ()));
+}
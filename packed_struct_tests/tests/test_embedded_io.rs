@@ -0,0 +1,82 @@
+use packed_struct::prelude::*;
+use packed_struct::io_embedded::{PackedIoError, ReadPacked, WritePacked};
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="lsb")]
+pub struct Telemetry {
+    #[packed_field(bits="0..=3")]
+    channel: Integer<u8, packed_bits::Bits::<4>>,
+    #[packed_field(bits="4")]
+    valid: bool,
+    #[packed_field(bytes="1..=2")]
+    reading: u16
+}
+
+#[test]
+fn test_embedded_io_roundtrip() {
+    let a = Telemetry { channel: 3.into(), valid: true, reading: 0x1234 };
+    let b = Telemetry { channel: 15.into(), valid: false, reading: 0xBEEF };
+
+    let mut stream = [0; 6];
+    {
+        let mut writer = &mut stream[..];
+        writer.write_packed(&a).unwrap();
+        writer.write_packed(&b).unwrap();
+        assert!(writer.is_empty());
+    }
+    assert_eq!([0b0011_1000, 0x34, 0x12, 0b1111_0000, 0xEF, 0xBE], stream);
+
+    let mut reader = &stream[..];
+    assert_eq!(a, reader.read_packed().unwrap());
+    assert_eq!(b, reader.read_packed().unwrap());
+    assert_eq!(Err(PackedIoError::UnexpectedEof), reader.read_packed::<Telemetry>());
+}
+
+#[test]
+fn test_embedded_io_errors() {
+    let mut reader = &[0xFF][..];
+    assert_eq!(Err(PackedIoError::Packing(PackingError::InvalidValue)), reader.read_packed::<bool>());
+
+    let mut small = [0; 2];
+    let mut writer = &mut small[..];
+    let res = writer.write_packed(&Telemetry { channel: 1.into(), valid: true, reading: 1 });
+    assert!(matches!(res, Err(PackedIoError::Io(_))));
+}
+
+#[test]
+fn test_embedded_io_async() {
+    use packed_struct::io_embedded::asynch;
+    use std::future::Future;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn noop_raw_waker() -> RawWaker {
+        fn clone(_: *const ()) -> RawWaker { noop_raw_waker() }
+        fn noop(_: *const ()) { }
+
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+
+    // slices never pend, so a single poll completes the whole future
+    fn poll_ready<F: Future>(f: F) -> F::Output {
+        let waker = unsafe { Waker::from_raw(noop_raw_waker()) };
+        let mut f = Box::pin(f);
+        match f.as_mut().poll(&mut Context::from_waker(&waker)) {
+            Poll::Ready(r) => r,
+            Poll::Pending => panic!("Slice based streams shouldn't pend")
+        }
+    }
+
+    let a = Telemetry { channel: 7.into(), valid: true, reading: 0xAA55 };
+
+    let mut stream = [0; 3];
+    {
+        let mut writer = &mut stream[..];
+        poll_ready(asynch::WritePacked::write_packed(&mut writer, &a)).unwrap();
+    }
+    assert_eq!([0b0111_1000, 0x55, 0xAA], stream);
+
+    let mut reader = &stream[..];
+    let unpacked: Telemetry = poll_ready(asynch::ReadPacked::read_packed(&mut reader)).unwrap();
+    assert_eq!(a, unpacked);
+}
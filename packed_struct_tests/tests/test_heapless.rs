@@ -0,0 +1,77 @@
+use packed_struct::prelude::*;
+use std::convert::TryFrom;
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Sample {
+    channel: u8,
+    value: u16
+}
+
+#[test]
+fn test_heapless_vec() {
+    let mut samples: heapless::Vec<Sample, 4> = heapless::Vec::new();
+    samples.push(Sample { channel: 1, value: 0x0203 }).unwrap();
+    samples.push(Sample { channel: 4, value: 0x0506 }).unwrap();
+
+    let mut buf = [0; 6];
+    samples.pack_to_slice(&mut buf).unwrap();
+    assert_eq!([1, 2, 3, 4, 5, 6], buf);
+    assert_eq!(samples, <heapless::Vec<Sample, 4>>::unpack_from_slice(&buf).unwrap());
+
+    assert_eq!(Err(PackingError::CapacityExceeded { capacity: 1, required: 2 }), <heapless::Vec<Sample, 1>>::unpack_from_slice(&buf));
+    assert_eq!(Err(PackingError::BufferModMismatch { actual_size: 5, modulo_required: 3 }), <heapless::Vec<Sample, 4>>::unpack_from_slice(&buf[..5]));
+}
+
+#[test]
+fn test_heapless_string() {
+    let s: heapless::String<8> = heapless::String::try_from("abc").unwrap();
+    let mut buf = [0; 3];
+    s.pack_to_slice(&mut buf).unwrap();
+    assert_eq!(*b"abc", buf);
+
+    assert_eq!(Ok(s), <heapless::String<8>>::unpack_from_slice(b"abc"));
+    assert_eq!(Err(PackingError::CapacityExceeded { capacity: 2, required: 3 }), <heapless::String<2>>::unpack_from_slice(b"abc"));
+    assert_eq!(Err(PackingError::InvalidValue), <heapless::String<8>>::unpack_from_slice(&[0xFF, 0xFE]));
+}
+
+#[test]
+fn test_heapless_tuple() {
+    type Message = (u8, LengthPrefixed<u8, heapless::String<8>>, heapless::Vec<u8, 4>);
+
+    let raw = [0x10, 2, b'h', b'i', 0xAA, 0xBB];
+    let (kind, name, payload) = Message::unpack_from_slice(&raw).unwrap();
+    assert_eq!(0x10, kind);
+    assert_eq!("hi", name.as_str());
+    assert_eq!(&[0xAA, 0xBB], &payload[..]);
+}
+
+#[derive(PackedStruct, Debug, PartialEq)]
+#[packed_struct(endian="msb")]
+pub struct Report {
+    id: u8,
+    num_samples: u8,
+    #[packed_field(count="num_samples")]
+    samples: heapless::Vec<Sample, 4>,
+    #[packed_field(length_prefix="u8")]
+    name: heapless::String<8>
+}
+
+#[test]
+fn test_heapless_derive() {
+    let mut report = Report { id: 9, num_samples: 0, samples: heapless::Vec::new(), name: heapless::String::try_from("ab").unwrap() };
+    report.samples.push(Sample { channel: 1, value: 0x0203 }).unwrap();
+
+    let mut buf = [0; 8];
+    report.pack_to_slice(&mut buf).unwrap();
+    assert_eq!([9, 1, 1, 2, 3, 2, b'a', b'b'], buf);
+
+    let unpacked = Report::unpack_from_slice(&buf).unwrap();
+    assert_eq!(1, unpacked.num_samples);
+    assert_eq!(report.samples, unpacked.samples);
+    assert_eq!(report.name, unpacked.name);
+
+    // more samples than the vector can hold
+    assert_eq!(Err(PackingError::CapacityExceeded { capacity: 4, required: 5 }),
+        Report::unpack_from_slice(&[9, 5, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).map(|_| ()));
+}
@@ -12,6 +12,8 @@ fn test_serialization_traits() {
     check_serde_support::<BitOne>();
     check_serde_support::<BitZero>();
     check_serde_support::<ReservedBits<u8, packed_bits::Bits::<8>>>();
+    check_serde_support::<ReservedBitsStrict<u8, packed_bits::Bits::<8>>>();
+    check_serde_support::<ReservedPreserve<packed_bits::Bits::<4>>>();
 }
 
 fn check_serde_support<'a, T>() 
@@ -0,0 +1,111 @@
+use packed_struct::prelude::*;
+use packed_struct::stream::PackedStreamDecoder;
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(bit_numbering="msb0", endian="msb")]
+pub struct Packet {
+    #[packed_field(bytes="0")]
+    magic: u8,
+    #[packed_field(bytes="1")]
+    version: u8,
+    #[packed_field(bytes="2..=3")]
+    value: u16,
+    #[packed_field(bytes="4")]
+    sum: u8
+}
+
+impl Packet {
+    fn new(value: u16) -> Self {
+        let mut p = Packet { magic: 0x7E, version: 1, value, sum: 0 };
+        let packed = p.pack().unwrap();
+        p.sum = sum(&packed[..4]);
+        p
+    }
+}
+
+fn sum(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0u8, |a, b| a.wrapping_add(*b))
+}
+
+fn sum_valid(frame: &[u8]) -> bool {
+    sum(&frame[..4]) == frame[4]
+}
+
+fn stream_of(packets: &[Packet]) -> Vec<u8> {
+    packets.iter().flat_map(|p| p.pack().unwrap().to_vec()).collect()
+}
+
+#[test]
+fn test_stream_decoder_chunks() {
+    let packets: Vec<_> = (0..20).map(|i| Packet::new(i * 1000)).collect();
+    let stream = stream_of(&packets);
+
+    for chunk_size in 1..12 {
+        let mut decoder = PackedStreamDecoder::<Packet, 8>::new().unwrap().with_sync(&[0x7E, 0x01]);
+        let mut decoded = vec![];
+        for chunk in stream.chunks(chunk_size) {
+            for frame in decoder.feed(chunk) {
+                decoded.push(frame.unwrap());
+            }
+        }
+        assert_eq!(packets, decoded);
+        assert_eq!(0, decoder.buffered());
+    }
+}
+
+#[test]
+fn test_stream_decoder_resync() {
+    let mut stream = vec![0x00, 0x7E, 0x7E];
+    stream.extend(stream_of(&[Packet::new(1)]));
+    // corrupted payload
+    let mut corrupted = stream_of(&[Packet::new(2)]);
+    corrupted[3] ^= 0x10;
+    stream.extend(corrupted);
+    // truncated packet, followed by a full one
+    stream.extend(&stream_of(&[Packet::new(3)])[..3]);
+    stream.extend(stream_of(&[Packet::new(4)]));
+
+    let mut decoder = PackedStreamDecoder::<Packet, 5>::new().unwrap()
+        .with_sync(&[0x7E])
+        .with_checksum(sum_valid);
+
+    let frames: Vec<_> = decoder.feed(&stream).collect();
+    let values: Vec<_> = frames.iter().filter_map(|f| f.as_ref().ok()).map(|p| p.value).collect();
+    assert_eq!(vec![1, 4], values);
+    assert!(frames.iter().any(|f| f == &Err(PackingError::ChecksumMismatch)));
+}
+
+#[test]
+fn test_stream_decoder_header_validation() {
+    let mut wrong_version = Packet::new(5);
+    wrong_version.version = 2;
+    let mut stream = stream_of(&[wrong_version]);
+    stream.extend(stream_of(&[Packet::new(6)]));
+
+    let mut decoder = PackedStreamDecoder::<Packet, 16>::new().unwrap()
+        .with_validation(|p| p.magic == 0x7E && p.version == 1);
+
+    let frames: Vec<_> = decoder.feed(&stream).map(|f| f.unwrap().value).collect();
+    assert_eq!(vec![6], frames);
+}
+
+#[test]
+fn test_stream_decoder_stop_early() {
+    let packets: Vec<_> = (0..3).map(|i| Packet::new(i + 10)).collect();
+    let stream = stream_of(&packets);
+
+    let mut decoder = PackedStreamDecoder::<Packet, 8>::new().unwrap().with_sync(&[0x7E, 0x01]);
+    let mut frames = decoder.feed(&stream);
+    assert_eq!(Some(Ok(packets[0])), frames.next());
+    // the rest of the chunk is handed back instead of being lost
+    let remaining = frames.remaining();
+    drop(frames);
+
+    let decoded: Vec<_> = decoder.feed(remaining).map(|f| f.unwrap()).collect();
+    assert_eq!(&packets[1..], &decoded[..]);
+}
+
+#[test]
+fn test_stream_decoder_buffer_too_small() {
+    assert_eq!(Err(PackingError::BufferTooSmall), PackedStreamDecoder::<Packet, 4>::new().map(|_| ()));
+}
@@ -0,0 +1,75 @@
+use bytes::BytesMut;
+use tokio_util::codec::{Decoder, Encoder};
+use packed_struct::prelude::*;
+use packed_struct::io_codec::{PackedCodec, PackedCodecError, PackedLengthCodec};
+
+#[derive(PackedStruct, Debug, Copy, Clone, PartialEq)]
+#[packed_struct(endian="lsb")]
+pub struct Sample {
+    channel: u8,
+    value: i16
+}
+
+#[test]
+fn test_packed_codec_partial_input() {
+    let mut codec = PackedCodec::<Sample>::new();
+
+    let mut encoded = BytesMut::new();
+    codec.encode(Sample { channel: 1, value: -2 }, &mut encoded).unwrap();
+    codec.encode(Sample { channel: 2, value: 300 }, &mut encoded).unwrap();
+    assert_eq!(&[0x01, 0xFE, 0xFF, 0x02, 0x2C, 0x01], &encoded[..]);
+
+    // feed the stream one byte at a time
+    let mut src = BytesMut::new();
+    let mut decoded = vec![];
+    for b in encoded.iter() {
+        src.extend_from_slice(&[*b]);
+        if let Some(s) = codec.decode(&mut src).unwrap() {
+            decoded.push(s);
+        }
+    }
+
+    assert_eq!(vec![Sample { channel: 1, value: -2 }, Sample { channel: 2, value: 300 }], decoded);
+    assert!(src.is_empty());
+}
+
+#[test]
+fn test_packed_codec_error() {
+    let mut codec = PackedCodec::<bool>::new();
+    let mut src = BytesMut::from(&[0x02][..]);
+    match codec.decode(&mut src) {
+        Err(PackedCodecError::Packing(PackingError::InvalidValue)) => (),
+        r => panic!("Unexpected result: {:?}", r)
+    }
+}
+
+#[test]
+fn test_packed_length_codec() {
+    type Message = (Sample, Vec<u8>);
+
+    let mut codec = PackedLengthCodec::<u8, Message>::new();
+    let mut buf = BytesMut::new();
+    codec.encode((Sample { channel: 3, value: 4 }, vec![0xAA, 0xBB]), &mut buf).unwrap();
+    codec.encode((Sample { channel: 5, value: 6 }, vec![]), &mut buf).unwrap();
+    assert_eq!(&[5, 0x03, 0x04, 0x00, 0xAA, 0xBB, 3, 0x05, 0x06, 0x00], &buf[..]);
+
+    assert_eq!(Some((Sample { channel: 3, value: 4 }, vec![0xAA, 0xBB])), codec.decode(&mut buf).unwrap());
+    assert_eq!(Some((Sample { channel: 5, value: 6 }, vec![])), codec.decode(&mut buf).unwrap());
+    assert_eq!(None, codec.decode(&mut buf).unwrap());
+
+    // the prefix can't describe this payload
+    let mut out = BytesMut::new();
+    let res = codec.encode((Sample { channel: 0, value: 0 }, vec![0; 300]), &mut out);
+    assert!(matches!(res, Err(PackedCodecError::Packing(PackingError::InvalidValue))));
+    assert!(out.is_empty());
+}
+
+#[test]
+fn test_packed_length_codec_max_length() {
+    let mut codec = PackedLengthCodec::<u8, Vec<u8>>::with_max_length(4);
+    let mut src = BytesMut::from(&[10, 0, 0][..]);
+    match codec.decode(&mut src) {
+        Err(PackedCodecError::FrameTooLarge { length: 10, max_length: 4 }) => (),
+        r => panic!("Unexpected result: {:?}", r)
+    }
+}